@@ -32,8 +32,13 @@ fn main() {
         // Get the SSA form for this function
         let il = il.ssa_form();
 
+        let Ok(blocks) = il.basic_blocks() else {
+            println!("    Does not have MLIL basic blocks\n");
+            continue;
+        };
+
         // Loop through all blocks in the function
-        for block in il.basic_blocks().iter() {
+        for block in blocks.iter() {
             // Loop though each instruction in the block
             for instr in block.iter() {
                 // Uplift the instruction into a native rust format
@@ -123,8 +123,13 @@ fn main() {
             continue;
         };
 
+        let Ok(blocks) = il.basic_blocks() else {
+            println!("    Does not have MLIL basic blocks\n");
+            continue;
+        };
+
         // Loop through all blocks in the function
-        for block in il.basic_blocks().iter() {
+        for block in blocks.iter() {
             // Loop though each instruction in the block
             for instr in block.iter() {
                 // Generically parse the IL tree and display the parts
@@ -42,6 +42,7 @@ use crate::function::{Function, NativeBlock};
 use crate::linearview::LinearDisassemblyLine;
 use crate::linearview::LinearViewCursor;
 use crate::metadata::Metadata;
+use crate::mlil::MediumLevelILFunction;
 use crate::platform::Platform;
 use crate::relocation::Relocation;
 use crate::section::{Section, SectionBuilder};
@@ -951,6 +952,17 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// Iterates the MLIL of every function in the view, skipping functions without MLIL.
+    ///
+    /// Only the list of function handles is fetched up front; each function's MLIL is
+    /// requested as the iterator reaches it.
+    fn medium_level_il_functions(&self) -> MediumLevelILFunctionIter {
+        MediumLevelILFunctionIter {
+            functions: self.functions(),
+            next: 0,
+        }
+    }
+
     /// List of functions *starting* at `addr`
     fn functions_at(&self, addr: u64) -> Array<Function> {
         unsafe {
@@ -1345,6 +1357,34 @@ pub trait BinaryViewExt: BinaryViewBase {
 
 impl<T: BinaryViewBase> BinaryViewExt for T {}
 
+/// Iterator over the MLIL of the functions in a view, see
+/// [BinaryViewExt::medium_level_il_functions].
+pub struct MediumLevelILFunctionIter {
+    functions: Array<Function>,
+    next: usize,
+}
+
+impl Iterator for MediumLevelILFunctionIter {
+    type Item = Ref<MediumLevelILFunction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.functions.len() {
+            let function = self.functions.get(self.next);
+            self.next += 1;
+            if let Ok(mlil) = function.medium_level_il() {
+                return Some(mlil);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.functions.len() - self.next))
+    }
+}
+
+impl core::iter::FusedIterator for MediumLevelILFunctionIter {}
+
 #[derive(PartialEq, Eq, Hash)]
 pub struct BinaryView {
     pub(crate) handle: *mut BNBinaryView,
@@ -141,6 +141,16 @@ impl FileMetadata {
         }
     }
 
+    /// Begins recording undo actions into a single undo entry, which is committed when the
+    /// returned [UndoGroup] is dropped. Unlike [FileMetadata::run_undoable_transaction] the
+    /// changes may be spread over any number of calls made while the guard is alive.
+    pub fn begin_undo_group(&self) -> UndoGroup {
+        UndoGroup {
+            file: self.to_owned(),
+            id: Some(self.begin_undo_actions(false)),
+        }
+    }
+
     pub fn begin_undo_actions(&self, anonymous_allowed: bool) -> BnString {
         unsafe { BnString::from_raw(BNBeginUndoActions(self.handle, anonymous_allowed)) }
     }
@@ -305,6 +315,31 @@ unsafe impl RefCountable for FileMetadata {
     }
 }
 
+/// Guard coalescing the undo actions recorded during its lifetime into one undo entry, see
+/// [FileMetadata::begin_undo_group].
+#[must_use]
+pub struct UndoGroup {
+    file: Ref<FileMetadata>,
+    id: Option<BnString>,
+}
+
+impl UndoGroup {
+    /// Reverts the changes recorded so far instead of committing them.
+    pub fn revert(mut self) {
+        if let Some(id) = self.id.take() {
+            self.file.revert_undo_actions(id);
+        }
+    }
+}
+
+impl Drop for UndoGroup {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.file.commit_undo_actions(id);
+        }
+    }
+}
+
 unsafe extern "C" fn cb_progress_func(
     ctxt: *mut ::std::os::raw::c_void,
     progress: usize,
@@ -121,6 +121,31 @@ impl BlockContext for NativeBlock {
     }
 }
 
+/// Operations shared by the IL forms of a function, so that a pass can be written once for
+/// [llil::Function], [mlil::MediumLevelILFunction] and [hlil::HighLevelILFunction].
+pub trait ILFunction {
+    type Instruction<'a>
+    where
+        Self: 'a;
+    type Block<'a>: BlockContext
+    where
+        Self: 'a;
+
+    fn instruction_count(&self) -> usize;
+
+    /// Returns the instruction with index `instr_idx`, which must be less than
+    /// [ILFunction::instruction_count].
+    ///
+    /// This takes an instruction index, not an expression index: for MLIL and HLIL it is the
+    /// same index as the inherent `instruction_from_instruction_idx`, not the inherent
+    /// `instruction_from_idx`.
+    fn instruction_at_index(&self, instr_idx: usize) -> Self::Instruction<'_>;
+
+    fn basic_blocks(&self) -> Result<Array<BasicBlock<Self::Block<'_>>>, ()>;
+
+    fn get_function(&self) -> Ref<Function>;
+}
+
 #[derive(Eq)]
 pub struct Function {
     pub(crate) handle: *mut BNFunction,
@@ -319,6 +344,20 @@ impl Function {
         Ok(unsafe { mlil::MediumLevelILFunction::ref_from_raw(mlil) })
     }
 
+    /// MediumLevelILFunction used to represent Function medium level IL
+    ///
+    /// Returns `Err` if the function has no medium level IL, e.g. if analysis only generated
+    /// low level IL for it. See [Function::medium_level_il_if_available] to avoid generating it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use binaryninja::function::Function;
+    /// # let func: Function = todo!();
+    /// let Ok(mlil) = func.medium_level_il() else {
+    ///     return;
+    /// };
+    /// println!("{} MLIL instructions", mlil.instruction_count());
+    /// ```
     pub fn medium_level_il(&self) -> Result<Ref<mlil::MediumLevelILFunction>, ()> {
         unsafe {
             let mlil = BNGetFunctionMediumLevelIL(self.handle);
@@ -11,7 +11,7 @@ use binaryninjacore_sys::BNHighLevelILFunction;
 use binaryninjacore_sys::BNNewHighLevelILFunctionReference;
 
 use crate::basicblock::BasicBlock;
-use crate::function::Function;
+use crate::function::{Function, ILFunction};
 use crate::rc::{Array, Ref, RefCountable};
 
 use super::{HighLevelILBlock, HighLevelILInstruction, HighLevelILLiftedInstruction};
@@ -124,6 +124,27 @@ impl HighLevelILFunction {
     }
 }
 
+impl ILFunction for HighLevelILFunction {
+    type Instruction<'a> = HighLevelILInstruction;
+    type Block<'a> = HighLevelILBlock;
+
+    fn instruction_count(&self) -> usize {
+        self.instruction_count()
+    }
+
+    fn instruction_at_index(&self, instr_idx: usize) -> HighLevelILInstruction {
+        self.instruction_from_instruction_idx(instr_idx)
+    }
+
+    fn basic_blocks(&self) -> Result<Array<BasicBlock<HighLevelILBlock>>, ()> {
+        Ok(self.basic_blocks())
+    }
+
+    fn get_function(&self) -> Ref<Function> {
+        self.get_function()
+    }
+}
+
 impl ToOwned for HighLevelILFunction {
     type Owned = Ref<Self>;
 
@@ -22,6 +22,7 @@ use std::marker::PhantomData;
 
 use crate::architecture::CoreArchitecture;
 use crate::basicblock::BasicBlock;
+use crate::function::ILFunction;
 use crate::rc::*;
 
 use super::*;
@@ -139,6 +140,22 @@ where
         }
     }
 
+    /// Returns the instruction with index `instr_idx`, or `None` if the index is out of range.
+    pub fn try_instruction_from_idx(&self, instr_idx: usize) -> Option<Instruction<A, M, F>> {
+        (instr_idx < self.instruction_count()).then(|| Instruction {
+            function: self,
+            instr_idx,
+        })
+    }
+
+    /// Iterates all instructions of this function in instruction index order.
+    pub fn instructions(&self) -> impl Iterator<Item = Instruction<A, M, F>> + '_ {
+        (0..self.instruction_count()).map(move |instr_idx| Instruction {
+            function: self,
+            instr_idx,
+        })
+    }
+
     pub fn instruction_count(&self) -> usize {
         unsafe {
             use binaryninjacore_sys::BNGetLowLevelILInstructionCount;
@@ -146,6 +163,13 @@ where
         }
     }
 
+    pub fn expr_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetLowLevelILExprCount;
+            BNGetLowLevelILExprCount(self.handle)
+        }
+    }
+
     pub fn get_function(&self) -> Ref<crate::function::Function> {
         unsafe {
             let func = BNGetLowLevelILOwnerFunction(self.handle);
@@ -175,6 +199,31 @@ where
     }
 }
 
+impl<A, F> ILFunction for Function<A, Finalized, F>
+where
+    A: Architecture,
+    F: FunctionForm,
+{
+    type Instruction<'a> = Instruction<'a, A, Finalized, F> where Self: 'a;
+    type Block<'a> = LowLevelBlock<'a, A, Finalized, F> where Self: 'a;
+
+    fn instruction_count(&self) -> usize {
+        self.instruction_count()
+    }
+
+    fn instruction_at_index(&self, instr_idx: usize) -> Instruction<A, Finalized, F> {
+        self.instruction_from_idx(instr_idx)
+    }
+
+    fn basic_blocks(&self) -> Result<Array<BasicBlock<LowLevelBlock<A, Finalized, F>>>, ()> {
+        Ok(self.basic_blocks())
+    }
+
+    fn get_function(&self) -> Ref<crate::function::Function> {
+        self.get_function()
+    }
+}
+
 // Allow instantiating Lifted IL functions for querying Lifted IL from Architectures
 impl Function<CoreArchitecture, Mutable, NonSSA<LiftedNonSSA>> {
     pub fn new(
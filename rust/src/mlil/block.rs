@@ -54,6 +54,14 @@ impl BlockContext for MediumLevelILBlock {
     }
 }
 
+impl BasicBlock<MediumLevelILBlock> {
+    /// Iterates the instructions of this block in block order. This is the same as
+    /// [BasicBlock::iter].
+    pub fn instructions(&self) -> MediumLevelILBlockIter {
+        self.iter()
+    }
+}
+
 impl Clone for MediumLevelILBlock {
     fn clone(&self) -> Self {
         MediumLevelILBlock {
@@ -1,16 +1,28 @@
 use core::hash::{Hash, Hasher};
-use std::ffi::c_char;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr};
+use std::ops::Range;
 
 use binaryninjacore_sys::*;
 
-use crate::architecture::CoreArchitecture;
+use crate::architecture::{Architecture, CoreArchitecture, Register};
 use crate::basicblock::BasicBlock;
-use crate::function::{Function, Location};
+use crate::binaryview::{AnalysisState, BinaryView, BinaryViewExt};
+use crate::filemetadata::UndoGroup;
+use crate::function::{Function, ILFunction, Location};
+use crate::hlil::{HighLevelILFunction, HighLevelILInstruction};
+use crate::llil::RegularFunction;
 use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner, Ref, RefCountable};
-use crate::string::BnStrCompatible;
-use crate::types::{Conf, PossibleValueSet, Type, UserVariableValues, Variable};
+use crate::string::{BnStrCompatible, BnString};
+use crate::types::{
+    max_confidence, Conf, PossibleValueSet, RegisterValue, RegisterValueType, SSAVariable, Type,
+    TypeClass, UserVariableValues, Variable, VariableSource,
+};
 
-use super::{MediumLevelILBlock, MediumLevelILInstruction, MediumLevelILLiftedInstruction};
+use super::{
+    MediumLevelILBlock, MediumLevelILInstruction, MediumLevelILInstructionKind,
+    MediumLevelILLiftedInstruction, MediumLevelILOperation,
+};
 
 pub struct MediumLevelILFunction {
     pub(crate) handle: *mut BNMediumLevelILFunction,
@@ -39,24 +51,68 @@ impl MediumLevelILFunction {
         Self { handle }.to_owned()
     }
 
+    /// Returns the instruction starting at `loc`. A location without an architecture is
+    /// looked up with the architecture of the owning function.
     pub fn instruction_at<L: Into<Location>>(&self, loc: L) -> Option<MediumLevelILInstruction> {
         let loc: Location = loc.into();
-        let arch_handle = loc.arch.unwrap();
+        let arch_handle = loc.arch.unwrap_or_else(|| self.get_function().arch());
 
-        let expr_idx =
+        let instr_idx =
             unsafe { BNMediumLevelILGetInstructionStart(self.handle, arch_handle.0, loc.addr) };
 
-        if expr_idx >= self.instruction_count() {
+        if instr_idx >= self.instruction_count() {
             None
         } else {
-            Some(MediumLevelILInstruction::new(self.to_owned(), expr_idx))
+            Some(self.instruction_from_instruction_idx(instr_idx))
         }
     }
 
+    /// Whether `addr` lies within the address ranges of the owning function. `addr` need not be
+    /// the start of an instruction.
+    ///
+    /// This fetches the ranges from the core on every call. To classify many addresses, fetch
+    /// them once with [MediumLevelILFunction::address_set] and query that instead.
+    pub fn contains_address(&self, addr: u64) -> bool {
+        self.address_set().contains(addr)
+    }
+
+    /// Snapshot of the address ranges of the owning function, for repeated membership tests
+    /// without further calls into the core. See [FunctionAddressSet::contains].
+    pub fn address_set(&self) -> FunctionAddressSet {
+        FunctionAddressSet::new(
+            self.get_function()
+                .address_ranges()
+                .iter()
+                .map(|range| range.start()..range.end())
+                .collect(),
+        )
+    }
+
+    /// Returns the index of the instruction starting at `addr`, looked up with the architecture
+    /// of the owning function, e.g. to name the target of a `goto`.
+    ///
+    /// This is an instruction index as taken by
+    /// [MediumLevelILFunction::instruction_from_instruction_idx], not an expression index.
+    pub fn label_at_address(&self, addr: u64) -> Option<usize> {
+        let arch = self.get_function().arch();
+        let instr_idx = unsafe { BNMediumLevelILGetInstructionStart(self.handle, arch.0, addr) };
+        (instr_idx < self.instruction_count()).then_some(instr_idx)
+    }
+
+    /// Returns the expression with index `expr_idx`.
+    ///
+    /// `expr_idx` must be less than [MediumLevelILFunction::expr_count]; this is not checked
+    /// and the core faults on out of range indices. See
+    /// [MediumLevelILFunction::try_instruction_from_idx] for a checked version.
     pub fn instruction_from_idx(&self, expr_idx: usize) -> MediumLevelILInstruction {
         MediumLevelILInstruction::new(self.to_owned(), expr_idx)
     }
 
+    /// Returns the expression with index `expr_idx`, or `None` if the index is out of range.
+    pub fn try_instruction_from_idx(&self, expr_idx: usize) -> Option<MediumLevelILInstruction> {
+        (expr_idx < self.expr_count()).then(|| self.instruction_from_idx(expr_idx))
+    }
+
     pub fn lifted_instruction_from_idx(&self, expr_idx: usize) -> MediumLevelILLiftedInstruction {
         self.instruction_from_idx(expr_idx).lift()
     }
@@ -78,12 +134,382 @@ impl MediumLevelILFunction {
         unsafe { BNGetMediumLevelILInstructionCount(self.handle) }
     }
 
+    /// Number of expressions in this function. Expression indices, as used by
+    /// [MediumLevelILFunction::instruction_from_idx], range over `0..expr_count()`.
+    pub fn expr_count(&self) -> usize {
+        unsafe { BNGetMediumLevelILExprCount(self.handle) }
+    }
+
+    /// Returns an iterator over all instructions of this function, in instruction index order.
+    pub fn instructions(&self) -> MediumLevelILInstructionIter {
+        MediumLevelILInstructionIter {
+            function: self.to_owned(),
+            range: 0..self.instruction_count(),
+        }
+    }
+
+    /// Returns the first instruction, in instruction index order, for which `pred` returns
+    /// `true`. Instructions after the match are never built.
+    pub fn find_instruction<F>(&self, mut pred: F) -> Option<MediumLevelILInstruction>
+    where
+        F: FnMut(&MediumLevelILInstruction) -> bool,
+    {
+        (0..self.instruction_count())
+            .map(|instr_idx| self.instruction_from_instruction_idx(instr_idx))
+            .find(|instr| pred(instr))
+    }
+
+    /// Returns the address of the machine instruction the instruction with index `instr_idx` was
+    /// lifted from. This is the inverse of [MediumLevelILFunction::instruction_at].
+    ///
+    /// Returns `None` if `instr_idx` is out of bounds or if the instruction was synthesized and
+    /// its address is not the start of a machine instruction of the owning function.
+    pub fn address_of_instruction(&self, instr_idx: usize) -> Option<u64> {
+        if instr_idx >= self.instruction_count() {
+            return None;
+        }
+        let addr = self.instruction_from_instruction_idx(instr_idx).address;
+        self.get_function()
+            .instruction_containing_address(addr, None)
+            .filter(|&start| start == addr)
+    }
+
+    /// Returns the index of every expression generated from the machine instruction at `addr`,
+    /// in expression index order. A single machine instruction can lift to many expressions,
+    /// e.g. a `rep movs` lifts to a whole loop.
+    ///
+    /// * `arch` - architecture of the machine instruction, the architecture of the owning
+    ///   function if `None`
+    /// * `addr` - address of the machine instruction
+    pub fn expr_indices_at(&self, arch: Option<CoreArchitecture>, addr: u64) -> Vec<usize> {
+        let arch = arch.unwrap_or_else(|| self.get_function().arch());
+        (0..self.expr_count())
+            .filter(|&expr_idx| {
+                unsafe { BNGetMediumLevelILByIndex(self.handle, expr_idx) }.address == addr
+            })
+            .filter(|&expr_idx| {
+                let instr_idx =
+                    unsafe { BNGetMediumLevelILInstructionForExpr(self.handle, expr_idx) };
+                self.instruction_arch(instr_idx) == Some(arch)
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over all instructions of this function lifted into their native rust
+    /// form, in instruction index order.
+    pub fn lifted_instructions(&self) -> impl Iterator<Item = MediumLevelILLiftedInstruction> {
+        self.instructions().map(|instr| instr.lift())
+    }
+
+    /// Returns every call instruction of this function, including tail calls, along with the
+    /// address of the callee if the dataflow resolved the call destination to a constant.
+    /// Indirect calls whose destination could not be resolved have no target.
+    pub fn call_sites(&self) -> Vec<(MediumLevelILInstruction, Option<u64>)> {
+        self.instructions()
+            .filter_map(|instr| {
+                let target = self.call_target(&instr)?;
+                Some((instr, target))
+            })
+            .collect()
+    }
+
+    /// Returns `None` if `instr` is not a call, otherwise the resolved call target, see
+    /// [MediumLevelILFunction::call_sites].
+    fn call_target(&self, instr: &MediumLevelILInstruction) -> Option<Option<u64>> {
+        use MediumLevelILInstructionKind::*;
+        let dest = match instr.kind {
+            Call(op) | Tailcall(op) => op.dest,
+            CallUntyped(op) | TailcallUntyped(op) => op.dest,
+            CallSsa(op) | TailcallSsa(op) => op.dest,
+            CallUntypedSsa(op) | TailcallUntypedSsa(op) => op.dest,
+            _ => return None,
+        };
+        Some(self.constant_expr_value(dest))
+    }
+
+    /// Returns the target of this function if it is a thunk: a single tail call or jump whose
+    /// destination the dataflow resolved to a constant address.
+    pub fn is_thunk(&self) -> Option<u64> {
+        use MediumLevelILInstructionKind::*;
+        if self.instruction_count() != 1 {
+            return None;
+        }
+        let dest = match self.instruction_from_instruction_idx(0).kind {
+            Jump(op) => op.dest,
+            Tailcall(op) => op.dest,
+            TailcallUntyped(op) => op.dest,
+            TailcallSsa(op) => op.dest,
+            TailcallUntypedSsa(op) => op.dest,
+            _ => return None,
+        };
+        self.constant_expr_value(dest)
+    }
+
+    /// Whether this function directly calls itself, including through a tail call. Only calls
+    /// whose destination the dataflow resolved to the start of this function count; mutual
+    /// recursion is not detected.
+    pub fn is_recursive(&self) -> bool {
+        let start = self.get_function().start();
+        self.call_sites()
+            .iter()
+            .any(|(_, target)| *target == Some(start))
+    }
+
+    /// Value of the expression `expr_idx` if the dataflow resolved it to a constant.
+    fn constant_expr_value(&self, expr_idx: usize) -> Option<u64> {
+        let value: RegisterValue =
+            unsafe { BNGetMediumLevelILExprValue(self.handle, expr_idx) }.into();
+        matches!(
+            value.state,
+            RegisterValueType::ConstantValue | RegisterValueType::ConstantPointerValue
+        )
+        .then_some(value.value as u64)
+    }
+
+    /// Returns the call sites, across all functions of the view, whose destination the dataflow
+    /// resolved to the start of this function. This is the inverse of
+    /// [MediumLevelILFunction::call_sites]. Callers without MLIL are skipped.
+    ///
+    /// Each reference points into the MLIL of its calling function, use
+    /// [ILReferenceSource::expr] to get the call instruction.
+    pub fn callers(&self) -> Vec<ILReferenceSource> {
+        let function = self.get_function();
+        let start = function.start();
+        let mut callers = vec![];
+        for code_ref in &function.view().get_code_refs(start) {
+            let Ok(caller) = code_ref.function().medium_level_il() else {
+                continue;
+            };
+            let arch = code_ref.architecture();
+            let Some(first) = caller.instruction_at((arch, code_ref.address)) else {
+                continue;
+            };
+            // A machine instruction can lift to several MLIL instructions, the call need not
+            // be the first of them.
+            let call = (first.instr_index()..caller.instruction_count())
+                .map(|instr_idx| caller.instruction_from_instruction_idx(instr_idx))
+                .take_while(|instr| instr.address == code_ref.address)
+                .find(|instr| caller.call_target(instr) == Some(Some(start)));
+            let Some(call) = call else {
+                continue;
+            };
+            callers.push(ILReferenceSource {
+                mlil: caller,
+                _func: code_ref.function().to_owned(),
+                _arch: arch,
+                addr: code_ref.address,
+                type_: ILFunctionKind::MediumLevelIL,
+                expr_id: call.index,
+            });
+        }
+        callers
+    }
+
+    /// Returns every instruction through which control returns to the caller: `MLIL_RET`,
+    /// `MLIL_RET_HINT` and tail calls. A function that does not return has no return sites.
+    pub fn return_sites(&self) -> Vec<MediumLevelILInstruction> {
+        use MediumLevelILInstructionKind::*;
+        self.instructions()
+            .filter(|instr| {
+                matches!(
+                    instr.kind,
+                    Ret(_)
+                        | RetHint(_)
+                        | Tailcall(_)
+                        | TailcallUntyped(_)
+                        | TailcallSsa(_)
+                        | TailcallUntypedSsa(_)
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the architecture of the basic block holding the instruction with index
+    /// `instr_idx`, or `None` if the instruction is not in any block.
+    fn instruction_arch(&self, instr_idx: usize) -> Option<CoreArchitecture> {
+        let block = unsafe { BNGetMediumLevelILBasicBlockForInstruction(self.handle, instr_idx) };
+        if block.is_null() {
+            return None;
+        }
+        let arch = unsafe { CoreArchitecture::from_raw(BNGetBasicBlockArchitecture(block)) };
+        unsafe { BNFreeBasicBlock(block) };
+        Some(arch)
+    }
+
+    /// Returns every instruction whose source address falls in `addr..addr + length`, in
+    /// instruction index order. A single machine instruction can lift to several MLIL
+    /// instructions, all of which are returned.
+    ///
+    /// * `arch` - architecture of the machine instructions, the architecture of the owning
+    ///   function if `None`
+    /// * `addr` - start of the address range
+    /// * `length` - length of the address range
+    pub fn instructions_in_range(
+        &self,
+        arch: Option<CoreArchitecture>,
+        addr: u64,
+        length: u64,
+    ) -> Vec<MediumLevelILInstruction> {
+        let arch = arch.unwrap_or_else(|| self.get_function().arch());
+        let range = addr..addr.saturating_add(length);
+        self.instructions()
+            .filter(|instr| range.contains(&instr.address))
+            .filter(|instr| self.instruction_arch(instr.instr_index()) == Some(arch))
+            .collect()
+    }
+
+    /// Iterates every `MLIL_CONST`, `MLIL_CONST_PTR` and `MLIL_EXTERN_PTR` expression of this
+    /// function, including those nested in other expressions such as comparison operands,
+    /// together with its value.
+    ///
+    /// Plain constants are sign extended from the size of the expression, the same way the core
+    /// renders negative constants. Pointers are kept unsigned, as the core renders addresses.
+    pub fn constants(
+        &self,
+    ) -> impl Iterator<Item = (MediumLevelILInstruction, MediumLevelILConstant)> {
+        use MediumLevelILInstructionKind::*;
+        self.instructions()
+            .flat_map(|instr| core::iter::once(instr.clone()).chain(instr.descendants()))
+            .filter_map(|expr| {
+                let value = match expr.kind {
+                    Const(op) => MediumLevelILConstant::Int(sign_extend(op.constant, expr.size)),
+                    ConstPtr(op) => MediumLevelILConstant::Pointer(op.constant),
+                    ExternPtr(op) => MediumLevelILConstant::Pointer(op.constant),
+                    _ => return None,
+                };
+                Some((expr, value))
+            })
+    }
+
+    /// Hashes the operation skeleton of this function: the operation of every instruction and
+    /// of each of its sub-expressions, in order, and the number of sub-expressions of every
+    /// instruction. Addresses, constants and variables are ignored, so functions that lift to
+    /// the same sequence of operations hash equal.
+    ///
+    /// The hash is computed with 64-bit FNV-1a over the core's operation numbers, so it is
+    /// stable across builds, Rust versions and processes and can be stored to match functions
+    /// across binaries. It only changes if the core renumbers its MLIL operations.
+    pub fn structural_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        for instr in self.instructions() {
+            hasher.write_u32(instr.operation() as u32);
+            let mut len = 0u64;
+            for expr in instr.descendants() {
+                hasher.write_u32(expr.operation() as u32);
+                len += 1;
+            }
+            hasher.write_u64(len);
+        }
+        hasher.finish()
+    }
+
+    /// Renders the text of every instruction of this function, one instruction per line, using
+    /// the architecture of the owning function.
+    pub fn text(&self) -> String {
+        let function = self.get_function();
+        let arch = function.arch();
+        let mut text = String::new();
+        for instr_idx in 0..self.instruction_count() {
+            let mut count = 0;
+            let mut tokens: *mut BNInstructionTextToken = core::ptr::null_mut();
+            let success = unsafe {
+                BNGetMediumLevelILInstructionText(
+                    self.handle,
+                    function.handle,
+                    arch.0,
+                    instr_idx,
+                    &mut tokens,
+                    &mut count,
+                    core::ptr::null_mut(),
+                )
+            };
+            if !success {
+                continue;
+            }
+            for token in unsafe { core::slice::from_raw_parts(tokens, count) } {
+                text.push_str(&unsafe { CStr::from_ptr(token.text) }.to_string_lossy());
+            }
+            text.push('\n');
+            unsafe { BNFreeInstructionText(tokens, count) };
+        }
+        text
+    }
+
     pub fn ssa_form(&self) -> MediumLevelILFunction {
         let ssa = unsafe { BNGetMediumLevelILSSAForm(self.handle) };
         assert!(!ssa.is_null());
         MediumLevelILFunction { handle: ssa }
     }
 
+    /// Returns the non-SSA form of this function, or `None` if the core does not provide one.
+    pub fn non_ssa_form(&self) -> Option<Ref<MediumLevelILFunction>> {
+        let non_ssa = unsafe { BNGetMediumLevelILNonSSAForm(self.handle) };
+        (!non_ssa.is_null()).then(|| unsafe { MediumLevelILFunction::ref_from_raw(non_ssa) })
+    }
+
+    /// Returns whether this is the SSA form of its function, as reported by the graph type of
+    /// its basic blocks. A function without basic blocks is never in SSA form.
+    pub fn is_ssa_form(&self) -> bool {
+        let Ok(blocks) = self.basic_blocks() else {
+            return false;
+        };
+        let Some(block) = blocks.iter().next() else {
+            return false;
+        };
+        matches!(
+            unsafe { BNGetBasicBlockFunctionGraphType(block.handle) },
+            BNFunctionGraphType::MediumLevelILSSAFormFunctionGraph
+                | BNFunctionGraphType::MappedMediumLevelILSSAFormFunctionGraph
+        )
+    }
+
+    /// Returns whether the owning function has no pending analysis update, i.e. whether user
+    /// changes such as [MediumLevelILFunction::set_user_var_value] have been analyzed.
+    ///
+    /// Reanalysis produces a new MLIL function, so once this returns `true` after a change the
+    /// MLIL should be fetched again with [Function::medium_level_il].
+    pub fn is_analysis_current(&self) -> bool {
+        !self.get_function().is_update_needed()
+    }
+
+    /// Updates the analysis of the whole view and blocks until it is done. The core can not wait
+    /// for a single function, so this is usually the way to make pending changes to this
+    /// function visible. See [MediumLevelILFunction::is_analysis_current].
+    pub fn update_and_wait(&self) {
+        self.get_function().view().update_analysis_and_wait()
+    }
+
+    /// Regenerates the SSA form of this function in place, without waiting for the rest of the
+    /// view to be reanalyzed. The call returns once the SSA form is rebuilt.
+    ///
+    /// * `analyze_conditionals` - whether to analyze conditional branches while building SSA
+    /// * `handle_aliases` - whether to track aliased variables
+    /// * `known_not_aliases` - variables known to never be aliased
+    /// * `known_aliases` - variables known to be aliased
+    pub fn generate_ssa_form(
+        &self,
+        analyze_conditionals: bool,
+        handle_aliases: bool,
+        known_not_aliases: &[Variable],
+        known_aliases: &[Variable],
+    ) {
+        let mut raw_known_not_aliases: Vec<BNVariable> =
+            known_not_aliases.iter().map(Variable::raw).collect();
+        let mut raw_known_aliases: Vec<BNVariable> =
+            known_aliases.iter().map(Variable::raw).collect();
+        unsafe {
+            BNGenerateMediumLevelILSSAForm(
+                self.handle,
+                analyze_conditionals,
+                handle_aliases,
+                raw_known_not_aliases.as_mut_ptr(),
+                raw_known_not_aliases.len(),
+                raw_known_aliases.as_mut_ptr(),
+                raw_known_aliases.len(),
+            )
+        }
+    }
+
     pub fn get_function(&self) -> Ref<Function> {
         unsafe {
             let func = BNGetMediumLevelILOwnerFunction(self.handle);
@@ -91,14 +517,167 @@ impl MediumLevelILFunction {
         }
     }
 
-    pub fn basic_blocks(&self) -> Array<BasicBlock<MediumLevelILBlock>> {
+    /// Returns the [BinaryView] containing the owning function, e.g. to read the data a
+    /// constant pointer in this function refers to.
+    pub fn view(&self) -> Ref<BinaryView> {
+        self.get_function().view()
+    }
+
+    /// Begins an undo group on the file of this function, so annotations such as
+    /// [MediumLevelILFunction::create_user_var] and [MediumLevelILFunction::set_user_var_value]
+    /// made while the guard is alive appear to the user as a single undo step.
+    ///
+    /// See [crate::filemetadata::FileMetadata::begin_undo_group].
+    pub fn begin_undo_group(&self) -> UndoGroup {
+        self.view().file().begin_undo_group()
+    }
+
+    /// Returns the LLIL function this MLIL function was generated from, if any.
+    pub fn low_level_il(&self) -> Option<Ref<RegularFunction<CoreArchitecture>>> {
+        let llil = unsafe { BNGetLowLevelILForMediumLevelIL(self.handle) };
+        (!llil.is_null())
+            .then(|| unsafe { RegularFunction::from_raw(self.get_function().arch(), llil) })
+    }
+
+    /// Returns the HLIL function generated from this MLIL function, if any.
+    pub fn high_level_il(&self, full_ast: bool) -> Option<Ref<HighLevelILFunction>> {
+        let hlil = unsafe { BNGetHighLevelILForMediumLevelIL(self.handle) };
+        (!hlil.is_null()).then(|| unsafe { HighLevelILFunction::ref_from_raw(hlil, full_ast) })
+    }
+
+    /// Returns the basic blocks of this function, or `Err` if the core could not provide them
+    /// (e.g. the function failed to analyze).
+    ///
+    /// The source and target of the blocks' [BasicBlock::incoming_edges] and
+    /// [BasicBlock::outgoing_edges] are MLIL blocks as well, so they can be iterated directly.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use binaryninja::mlil::MediumLevelILFunction;
+    /// # let mlil_fun: MediumLevelILFunction = todo!();
+    /// for block in &mlil_fun.basic_blocks().unwrap() {
+    ///     for edge in &block.outgoing_edges() {
+    ///         let first_instr = edge.target().iter().next();
+    ///         println!("{:?} -> {:?}", edge.branch_type(), first_instr);
+    ///     }
+    /// }
+    /// ```
+    pub fn basic_blocks(&self) -> Result<Array<BasicBlock<MediumLevelILBlock>>, ()> {
         let mut count = 0;
         let blocks = unsafe { BNGetMediumLevelILBasicBlockList(self.handle, &mut count) };
+        if blocks.is_null() {
+            return Err(());
+        }
         let context = MediumLevelILBlock {
             function: self.to_owned(),
         };
 
-        unsafe { Array::new(blocks, count, context) }
+        Ok(unsafe { Array::new(blocks, count, context) })
+    }
+
+    /// Basic blocks in reverse postorder of the control flow graph, starting at the entry block.
+    ///
+    /// Blocks unreachable from the entry follow the reverse postorder of the reachable ones,
+    /// each unvisited block in index order starting a reverse postorder of its own, so every
+    /// block appears exactly once. Irreducible control flow still yields a valid ordering, just
+    /// not a unique one.
+    pub fn basic_blocks_rpo(&self) -> Result<Vec<Ref<BasicBlock<MediumLevelILBlock>>>, ()> {
+        let blocks = self.basic_blocks()?;
+        let blocks: Vec<Ref<BasicBlock<MediumLevelILBlock>>> = blocks
+            .iter()
+            .map(|block| BasicBlock::to_owned(&block))
+            .collect();
+        let position: HashMap<usize, usize> = blocks
+            .iter()
+            .enumerate()
+            .map(|(pos, block)| (block.index(), pos))
+            .collect();
+        let successors: Vec<Vec<usize>> = blocks
+            .iter()
+            .map(|block| {
+                block
+                    .outgoing_edges()
+                    .iter()
+                    .filter_map(|edge| position.get(&edge.target().index()).copied())
+                    .collect()
+            })
+            .collect();
+
+        Ok(reverse_postorder(&successors)
+            .into_iter()
+            .map(|pos| blocks[pos].clone())
+            .collect())
+    }
+
+    /// Immediate dominator of `block`, or `None` for the entry block.
+    pub fn immediate_dominator(
+        &self,
+        block: &BasicBlock<MediumLevelILBlock>,
+    ) -> Option<Ref<BasicBlock<MediumLevelILBlock>>> {
+        // Guard against the core reporting the entry block as its own dominator.
+        block
+            .immediate_dominator()
+            .filter(|dom| dom.index() != block.index())
+    }
+
+    /// Blocks immediately dominated by `block`.
+    pub fn dominator_tree_children(
+        &self,
+        block: &BasicBlock<MediumLevelILBlock>,
+    ) -> Array<BasicBlock<MediumLevelILBlock>> {
+        block.dominator_tree_children()
+    }
+
+    /// Dominance frontier of `block`.
+    pub fn dominance_frontier(
+        &self,
+        block: &BasicBlock<MediumLevelILBlock>,
+    ) -> Array<BasicBlock<MediumLevelILBlock>> {
+        block.dominance_frontier()
+    }
+
+    /// Gathers instruction, block, call and variable counts of this function in one call.
+    ///
+    /// Returns `Err` if the basic blocks or variables of the function are not available.
+    pub fn summary(&self) -> Result<MediumLevelILFunctionSummary, ()> {
+        let has_ssa = self.is_ssa_form() || {
+            let ssa = unsafe { BNGetMediumLevelILSSAForm(self.handle) };
+            if !ssa.is_null() {
+                unsafe { BNFreeMediumLevelILFunction(ssa) };
+            }
+            !ssa.is_null()
+        };
+        // call_sites only covers calls and tail calls.
+        let syscall_count = self
+            .instructions()
+            .filter(|instr| {
+                use MediumLevelILInstructionKind::*;
+                matches!(
+                    instr.kind,
+                    Syscall(_) | SyscallSsa(_) | SyscallUntyped(_) | SyscallUntypedSsa(_)
+                )
+            })
+            .count();
+        Ok(MediumLevelILFunctionSummary {
+            instruction_count: self.instruction_count(),
+            basic_block_count: self.basic_blocks()?.len(),
+            call_count: self.call_sites().len() + syscall_count,
+            variable_count: self.variables()?.len(),
+            has_ssa,
+        })
+    }
+
+    /// Counts how often each operation occurs in this function, including the operations of
+    /// nested expressions, e.g. an `MLIL_ADD` computing the address of a store.
+    pub fn operation_histogram(&self) -> HashMap<MediumLevelILOperation, usize> {
+        let mut histogram = HashMap::new();
+        for instr in self.instructions() {
+            *histogram.entry(instr.operation()).or_insert(0) += 1;
+            for expr in instr.descendants() {
+                *histogram.entry(expr.operation()).or_insert(0) += 1;
+            }
+        }
+        histogram
     }
 
     pub fn get_var_definitions<'a>(&'a self, var: &Variable) -> MediumLevelILInstructionList<'a> {
@@ -114,8 +693,272 @@ impl MediumLevelILFunction {
         }
     }
 
+    /// Returns every instruction that uses `var`, empty if it has no uses.
+    pub fn get_var_uses<'a>(&'a self, var: &Variable) -> MediumLevelILInstructionList<'a> {
+        let mut count = 0;
+        let raw_instrs =
+            unsafe { BNGetMediumLevelILVariableUses(self.handle, &var.raw(), &mut count) };
+        let instrs = if raw_instrs.is_null() || count == 0 {
+            &[]
+        } else {
+            unsafe { core::slice::from_raw_parts(raw_instrs, count) }
+        };
+        MediumLevelILInstructionList {
+            mlil: self,
+            ptr: raw_instrs,
+            instr_idxs: instrs.iter(),
+        }
+    }
+
+    /// Returns the SSA variable for the given version of `var`.
+    ///
+    /// Use [MediumLevelILInstruction::get_ssa_var_version] to find the version of `var` that is
+    /// live at a given instruction.
+    pub fn get_ssa_var_for_variable(&self, var: &Variable, version: usize) -> SSAVariable {
+        SSAVariable::new(*var, version)
+    }
+
+    /// Returns the non-SSA variable underlying the given SSA variable.
+    pub fn get_variable_for_ssa_var(&self, ssa_var: &SSAVariable) -> Variable {
+        ssa_var.variable
+    }
+
+    /// Returns whether `var` is live at the instruction with index `instr_idx`, including the
+    /// instruction of its last use. Parameters are live from the start of the function up to
+    /// their last use. Returns `false` if the core could not provide the liveness data.
+    ///
+    /// * `var` - variable to query
+    /// * `instr_idx` - instruction index to query
+    pub fn is_var_live_at(&self, var: &Variable, instr_idx: usize) -> bool {
+        let mut count = 0;
+        let raw_instrs = unsafe {
+            BNGetMediumLevelILLiveInstructionsForVariable(self.handle, &var.raw(), true, &mut count)
+        };
+        if raw_instrs.is_null() {
+            return false;
+        }
+        let is_live =
+            unsafe { core::slice::from_raw_parts(raw_instrs, count) }.contains(&instr_idx);
+        unsafe { BNFreeILInstructionList(raw_instrs) };
+        is_live
+    }
+
+    /// Returns whether current dataflow proves that the instruction with index `instr_idx` can
+    /// never execute, because it is control dependent on an `MLIL_IF` whose condition resolved
+    /// to a constant that takes the other edge.
+    ///
+    /// MLIL keeps such instructions; they are only removed when HLIL is generated. Returns
+    /// `false` if the core could not provide the control dependencies.
+    ///
+    /// * `instr_idx` - instruction index to query
+    pub fn is_instruction_dead(&self, instr_idx: usize) -> bool {
+        use BNILBranchDependence::*;
+        let mut count = 0;
+        let raw_deps =
+            unsafe { BNGetAllMediumLevelILBranchDependence(self.handle, instr_idx, &mut count) };
+        if raw_deps.is_null() {
+            return false;
+        }
+        let is_dead = unsafe { core::slice::from_raw_parts(raw_deps, count) }
+            .iter()
+            .any(|dep| {
+                let MediumLevelILInstructionKind::If(op) =
+                    self.instruction_from_instruction_idx(dep.branch).kind
+                else {
+                    return false;
+                };
+                let value: RegisterValue =
+                    unsafe { BNGetMediumLevelILExprValue(self.handle, op.condition) }.into();
+                if value.state != RegisterValueType::ConstantValue {
+                    return false;
+                }
+                match dep.dependence {
+                    TrueBranchDependent => value.value == 0,
+                    FalseBranchDependent => value.value != 0,
+                    NotBranchDependent => false,
+                }
+            });
+        unsafe { BNFreeILBranchDependenceList(raw_deps) };
+        is_dead
+    }
+
+    /// Returns the instruction that defines the given SSA variable, or `None` if the variable
+    /// has no definition in this function (e.g. a parameter entering the function as version 0).
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn get_ssa_var_definition(
+        &self,
+        ssa_var: &SSAVariable,
+    ) -> Option<MediumLevelILInstruction> {
+        let instr_idx = unsafe {
+            BNGetMediumLevelILSSAVarDefinition(
+                self.handle,
+                &ssa_var.variable.raw(),
+                ssa_var.version,
+            )
+        };
+        (instr_idx < self.instruction_count())
+            .then(|| self.instruction_from_instruction_idx(instr_idx))
+    }
+
+    /// Returns all instructions that use the given SSA variable.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form]. Returns `Err`
+    /// if the core could not provide the uses.
+    pub fn get_ssa_var_uses(
+        &self,
+        ssa_var: &SSAVariable,
+    ) -> Result<Array<MediumLevelILInstruction>, ()> {
+        let mut count = 0;
+        let uses = unsafe {
+            BNGetMediumLevelILSSAVarUses(
+                self.handle,
+                &ssa_var.variable.raw(),
+                ssa_var.version,
+                &mut count,
+            )
+        };
+        if uses.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(uses, count, self.to_owned()) })
+    }
+
+    /// Returns every SSA version of `var`, in ascending order. This includes versions that are
+    /// only defined by a phi node, e.g. at a loop header, so the largest version bounds every
+    /// version appearing in this function. Empty if the core could not provide the versions.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn ssa_var_versions(&self, var: &Variable) -> Vec<usize> {
+        let mut count = 0;
+        let raw_versions =
+            unsafe { BNGetMediumLevelILVariableSSAVersions(self.handle, &var.raw(), &mut count) };
+        if raw_versions.is_null() {
+            return vec![];
+        }
+        let mut versions = unsafe { core::slice::from_raw_parts(raw_versions, count) }.to_vec();
+        unsafe { BNFreeILInstructionList(raw_versions) };
+        versions.sort_unstable();
+        versions
+    }
+
+    /// Returns the defining instruction of every SSA version of `var`, paired with the version
+    /// and in ascending version order. Versions defined by a phi node are paired with the
+    /// `MLIL_VAR_PHI`; versions without a definition, such as a parameter's value on entry,
+    /// are omitted.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn ssa_definitions(&self, var: &Variable) -> Vec<(usize, MediumLevelILInstruction)> {
+        self.ssa_var_versions(var)
+            .into_iter()
+            .filter_map(|version| {
+                let def = self.get_ssa_var_definition(&SSAVariable::new(*var, version))?;
+                Some((version, def))
+            })
+            .collect()
+    }
+
+    /// Returns the instruction that defines memory version `version`, or `None` if the version
+    /// has no definition, e.g. the memory state on entry.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn get_ssa_memory_definition(&self, version: usize) -> Option<MediumLevelILInstruction> {
+        let instr_idx = unsafe { BNGetMediumLevelILSSAMemoryDefinition(self.handle, version) };
+        (instr_idx < self.instruction_count())
+            .then(|| self.instruction_from_instruction_idx(instr_idx))
+    }
+
+    /// Returns all instructions that use memory version `version`.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form]. Returns `Err`
+    /// if the core could not provide the uses.
+    pub fn get_ssa_memory_uses(
+        &self,
+        version: usize,
+    ) -> Result<Array<MediumLevelILInstruction>, ()> {
+        let mut count = 0;
+        let uses = unsafe { BNGetMediumLevelILSSAMemoryUses(self.handle, version, &mut count) };
+        if uses.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(uses, count, self.to_owned()) })
+    }
+
+    /// Returns the value computed by the dataflow for the given SSA variable.
+    ///
+    /// This is a single [RegisterValue] rather than a [PossibleValueSet], since that is all the
+    /// core reports for this query; a range or set of values comes back as undetermined. Use
+    /// [MediumLevelILFunction::get_possible_ssa_var_values] for the full set.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn get_ssa_var_value(&self, ssa_var: &SSAVariable) -> RegisterValue {
+        unsafe {
+            BNGetMediumLevelILSSAVarValue(self.handle, &ssa_var.variable.raw(), ssa_var.version)
+        }
+        .into()
+    }
+
+    /// Returns the set of values the dataflow computed for the given SSA variable. This is the
+    /// set counterpart of [MediumLevelILFunction::get_ssa_var_value]: it can also describe
+    /// ranges and sets of values. Unlike [MediumLevelILFunction::get_possible_var_values] it is
+    /// not merged with the other versions of the variable.
+    ///
+    /// This function must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn get_possible_ssa_var_values(&self, ssa_var: &SSAVariable) -> PossibleValueSet {
+        let raw_var = ssa_var.variable.raw();
+        let def_idx = self
+            .get_ssa_var_definition(ssa_var)
+            .map_or(0, |def| def.instr_index());
+        let mut raw_values = unsafe {
+            BNGetMediumLevelILPossibleSSAVarValues(
+                self.handle,
+                &raw_var,
+                ssa_var.version,
+                def_idx,
+                core::ptr::null_mut(),
+                0,
+            )
+        };
+        let values = unsafe { PossibleValueSet::from_raw(raw_values) };
+        unsafe { BNFreePossibleValueSet(&mut raw_values) };
+        values
+    }
+
+    /// Returns the set of values the dataflow computed for `var` at the given instruction, merged
+    /// across all paths reaching it.
+    ///
+    /// This function must not be in SSA form, the query is performed on its SSA form using the
+    /// version of `var` live at `instr`.
+    ///
+    /// * `var` - Variable to query
+    /// * `instr` - Instruction of this function at which to query the value
+    pub fn get_possible_var_values(
+        &self,
+        var: &Variable,
+        instr: &MediumLevelILInstruction,
+    ) -> PossibleValueSet {
+        let raw_var = var.raw();
+        let instr_idx = instr.instr_index();
+        let ssa = self.ssa_form();
+        let version = instr.get_ssa_var_version(var);
+        let mut raw_values = unsafe {
+            let ssa_instr_idx = BNGetMediumLevelILSSAInstructionIndex(self.handle, instr_idx);
+            BNGetMediumLevelILPossibleSSAVarValues(
+                ssa.handle,
+                &raw_var,
+                version,
+                ssa_instr_idx,
+                core::ptr::null_mut(),
+                0,
+            )
+        };
+        let values = unsafe { PossibleValueSet::from_raw(raw_values) };
+        unsafe { BNFreePossibleValueSet(&mut raw_values) };
+        values
+    }
+
     pub fn create_user_stack_var<'a, S: BnStrCompatible, C: Into<Conf<&'a Type>>>(
-        self,
+        &self,
         offset: i64,
         var_type: C,
         name: S,
@@ -133,10 +976,104 @@ impl MediumLevelILFunction {
         }
     }
 
-    pub fn delete_user_stack_var(self, offset: i64) {
+    pub fn delete_user_stack_var(&self, offset: i64) {
         unsafe { BNDeleteUserStackVariable(self.get_function().handle, offset) }
     }
 
+    /// Stack variables of the function paired with their frame offset, sorted by offset.
+    ///
+    /// The offset is the same one `create_user_stack_var` and `delete_user_stack_var` take.
+    /// Variables sharing an offset keep the order the core reported them in.
+    pub fn stack_layout(&self) -> Vec<(i64, Variable, Option<Conf<Ref<Type>>>)> {
+        let mut layout: Vec<_> = self
+            .get_function()
+            .stack_layout()
+            .iter()
+            .map(|var| {
+                let variable = var.var();
+                let var_type = var
+                    .has_type()
+                    .then(|| Conf::new(var.var_type(), var.type_confidence()));
+                (variable.storage, variable, var_type)
+            })
+            .collect();
+        layout.sort_by_key(|(offset, _, _)| *offset);
+        layout
+    }
+
+    /// Size in bytes of the stack frame below the stack pointer at function entry.
+    ///
+    /// The core has no direct query for this, so it is derived from [Self::stack_layout] as the
+    /// distance to the lowest stack variable; a function without locals has a frame size of 0.
+    pub fn stack_frame_size(&self) -> i64 {
+        self.stack_layout()
+            .iter()
+            .map(|(offset, _, _)| -offset)
+            .max()
+            .unwrap_or(0)
+            .max(0)
+    }
+
+    /// Stack adjustment applied by the owning function on return, with the core's confidence in it.
+    pub fn stack_adjustment(&self) -> Conf<i64> {
+        self.get_function().stack_adjustment()
+    }
+
+    /// Sets the comment at the address of `instr` in the owning function.
+    ///
+    /// Returns `Err` without setting anything if the address of `instr` is not inside a basic
+    /// block of the function, e.g. for instructions synthesized by analysis.
+    pub fn set_comment_at<S: BnStrCompatible>(
+        &self,
+        instr: &MediumLevelILInstruction,
+        comment: S,
+    ) -> Result<(), ()> {
+        let function = self.get_function();
+        function
+            .basic_block_containing(instr.address, None)
+            .ok_or(())?;
+        function.set_comment_at(instr.address, comment);
+        Ok(())
+    }
+
+    /// Sets the parameters of the owning function to `vars`, in order, as a user override. An
+    /// empty slice makes the function take no parameters.
+    ///
+    /// This triggers reanalysis of the function, after which the IL of this function may be
+    /// stale.
+    pub fn set_parameter_variables(&self, vars: &[Variable]) {
+        self.get_function()
+            .set_user_parameter_variables(vars.iter().copied(), max_confidence())
+    }
+
+    /// Applies `func_type` as the user type of the owning function, setting its parameter and
+    /// return types in one step.
+    ///
+    /// Parameters with an explicit location in `func_type` are placed there; otherwise the
+    /// currently detected parameter variables are reused positionally. If `func_type` has fewer
+    /// parameters than are currently detected, the extra parameter variables are dropped.
+    ///
+    /// Returns `Err` if `func_type` is not a function type. This triggers reanalysis of the
+    /// function, after which the IL of this function may be stale.
+    pub fn apply_function_type(&self, func_type: &Type) -> Result<(), ()> {
+        if func_type.type_class() != TypeClass::FunctionTypeClass {
+            return Err(());
+        }
+        let params = func_type.parameters()?;
+        let function = self.get_function();
+        let detected = function.parameter_variables().contents;
+
+        function.set_user_type(func_type);
+        if params.iter().all(|param| param.location.is_some()) {
+            let vars = params.iter().filter_map(|param| param.location);
+            function.set_user_parameter_variables(vars, max_confidence());
+        } else if detected.len() > params.len() {
+            let vars = detected.into_iter().take(params.len());
+            function.set_user_parameter_variables(vars, max_confidence());
+        }
+        Ok(())
+    }
+
     pub fn create_user_var<'a, S: BnStrCompatible, C: Into<Conf<&'a Type>>>(
         &self,
         var: &Variable,
@@ -158,14 +1095,117 @@ impl MediumLevelILFunction {
         }
     }
 
+    /// Returns the type currently assigned to `var` and its confidence, or `None` if the
+    /// variable has no type. This is the read counterpart of [Self::create_user_var].
+    pub fn get_variable_type(&self, var: &Variable) -> Option<Conf<Ref<Type>>> {
+        let result = unsafe { BNGetVariableType(self.get_function().handle, &var.raw()) };
+        (!result.type_.is_null()).then(|| result.into())
+    }
+
+    /// Renames `var` as a user variable, keeping its current type and confidence.
+    ///
+    /// Returns `Err` if the variable has no type to preserve.
+    pub fn set_user_var_name<S: BnStrCompatible>(&self, var: &Variable, name: S) -> Result<(), ()> {
+        let var_type = self.get_variable_type(var).ok_or(())?;
+        self.create_user_var(var, &var_type, name, false);
+        Ok(())
+    }
+
     pub fn delete_user_var(&self, var: &Variable) {
         unsafe { BNDeleteUserVariable(self.get_function().handle, &var.raw()) }
     }
 
+    /// Returns the name of `var` as displayed by the core, including auto-generated names such
+    /// as `var_8`.
+    pub fn get_var_name(&self, var: &Variable) -> BnString {
+        self.get_function().get_variable_name(var)
+    }
+
     pub fn is_var_user_defined(&self, var: &Variable) -> bool {
         unsafe { BNIsVariableUserDefined(self.get_function().handle, &var.raw()) }
     }
 
+    /// Returns every variable the core tracks for this function, including variables without
+    /// any uses, such as unused parameters. Returns `Err` if the core could not provide them.
+    pub fn variables(&self) -> Result<Array<Variable>, ()> {
+        let mut count = 0;
+        let vars = unsafe { BNGetMediumLevelILVariables(self.handle, &mut count) };
+        if vars.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(vars, count, ()) })
+    }
+
+    /// Returns the variables that were merged into `var`, empty if `var` is atomic.
+    ///
+    /// The core only records decomposition in the form of variable merges, e.g. the two halves
+    /// of a 128-bit value that [Function::merge_variables] combined into one; it does not track
+    /// pieces of a variable that was never merged.
+    pub fn variable_components(&self, var: &Variable) -> Vec<Variable> {
+        // The first variable is `var` itself, the rest are the merge sources.
+        self.mlil_vars_for_hlil_var(var).split_off(1)
+    }
+
+    /// Returns the name of the register backing `var` in the architecture of the owning
+    /// function, e.g. `rax`, or `None` if `var` is a stack or flag variable.
+    pub fn register_name(&self, var: &Variable) -> Option<String> {
+        let VariableSource::Register(reg_id) = var.source() else {
+            return None;
+        };
+        let reg = self.get_function().arch().register_from_id(reg_id)?;
+        Some(reg.name().into_owned())
+    }
+
+    /// Returns the variables of this function that are aliased, e.g. locals whose address is
+    /// taken. Stores through pointers can modify these variables, which limits what the dataflow
+    /// can determine about their values. Returns `Err` if the core could not provide them.
+    pub fn aliased_variables(&self) -> Result<Array<Variable>, ()> {
+        let mut count = 0;
+        let vars = unsafe { BNGetMediumLevelILAliasedVariables(self.handle, &mut count) };
+        if vars.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(vars, count, ()) })
+    }
+
+    /// Returns the MLIL variables that back the HLIL variable `hlil_var`: the variable itself
+    /// followed by every variable merged into it, see [Function::merge_variables].
+    ///
+    /// * `hlil_var` - HLIL variable to query
+    pub fn mlil_vars_for_hlil_var(&self, hlil_var: &Variable) -> Vec<Variable> {
+        let mut vars = vec![*hlil_var];
+        for merged in &self.get_function().merged_variables() {
+            if merged.target() == *hlil_var {
+                vars.extend(merged.sources().iter());
+            }
+        }
+        vars
+    }
+
+    /// Splits a variable at the definition site. The given `var` must be the variable unique to
+    /// the definition and should be obtained by using
+    /// [MediumLevelILInstruction::get_split_var_for_definition] at the definition site.
+    ///
+    /// .. warning:: Splitting a variable triggers a reanalysis of the function, after which
+    /// [MediumLevelILFunction::get_var_definitions] reflects the split. See
+    /// [Function::split_variable] for the caveats of splitting variables manually.
+    ///
+    /// * `var` - variable to split
+    pub fn split_var(&self, var: &Variable) {
+        self.get_function().split_variable(var)
+    }
+
+    /// Undoes variable splitting performed with [MediumLevelILFunction::split_var]. The given
+    /// `var` must be the variable unique to the definition and should be obtained by using
+    /// [MediumLevelILInstruction::get_split_var_for_definition] at the definition site.
+    ///
+    /// .. warning:: Unsplitting a variable triggers a reanalysis of the function.
+    ///
+    /// * `var` - variable to unsplit
+    pub fn unsplit_var(&self, var: &Variable) {
+        self.get_function().unsplit_variable(var)
+    }
+
     /// Allows the user to specify a PossibleValueSet value for an MLIL
     /// variable at its definition site.
     ///
@@ -175,16 +1215,27 @@ impl MediumLevelILFunction {
     /// whose values can be determined statically will be computed, leading to
     /// potential branch elimination at the HLIL layer.
     ///
+    /// The value is stored as a user edit and persisted with the database. The core does not
+    /// provide an auto (non-persistent) counterpart for variable values.
+    ///
+    /// The core always feeds user values into the dataflow and has no option to store a value
+    /// without propagating it, so there is no way to keep a purely informational value from
+    /// eliminating branches. Record such annotations elsewhere, e.g. with
+    /// [MediumLevelILFunction::set_comment_at].
+    ///
     /// * `var` - Variable for which the value is to be set
     /// * `addr` - Address of the definition site of the variable
     /// * `value` - Informed value of the variable
     ///
+    /// Returns [SetVarValueError::NoDefinitionAtAddress] if `var` is not defined at `addr`.
+    ///
     /// # Example
     /// ```no_run
     /// # use binaryninja::mlil::MediumLevelILFunction;
     /// # use binaryninja::types::PossibleValueSet;
     /// # let mlil_fun: MediumLevelILFunction = todo!();
-    /// let (mlil_var, arch_addr, _val) = mlil_fun.user_var_values().all().next().unwrap();
+    /// let user_var_values = mlil_fun.user_var_values().unwrap();
+    /// let (mlil_var, arch_addr, _val) = user_var_values.all().next().unwrap();
     /// let def_address = arch_addr.address;
     /// let var_value = PossibleValueSet::ConstantValue{value: 5};
     /// mlil_fun.set_user_var_value(&mlil_var, def_address, var_value).unwrap();
@@ -194,13 +1245,12 @@ impl MediumLevelILFunction {
         var: &Variable,
         addr: u64,
         value: PossibleValueSet,
-    ) -> Result<(), ()> {
+    ) -> Result<(), SetVarValueError> {
         let Some(_def_site) = self
             .get_var_definitions(var)
             .find(|def| def.address == addr)
         else {
-            // Error "No definition for Variable found at given address"
-            return Err(());
+            return Err(SetVarValueError::NoDefinitionAtAddress);
         };
         let function = self.get_function();
         let def_site = BNArchitectureAndAddress {
@@ -213,6 +1263,72 @@ impl MediumLevelILFunction {
         Ok(())
     }
 
+    /// Like [MediumLevelILFunction::set_user_var_value], but takes the defining instruction
+    /// itself instead of its address, so the definition is matched exactly instead of by the
+    /// first definition found at the address.
+    ///
+    /// The core stores user values by definition address, so definitions sharing an address
+    /// also share the value.
+    ///
+    /// Returns [SetVarValueError::NotADefinition] if `def_site` is not a definition of `var`.
+    pub fn set_user_var_value_at_def(
+        &self,
+        var: &Variable,
+        def_site: &MediumLevelILInstruction,
+        value: PossibleValueSet,
+    ) -> Result<(), SetVarValueError> {
+        if !self
+            .get_var_definitions(var)
+            .any(|def| def.index == def_site.index)
+        {
+            return Err(SetVarValueError::NotADefinition);
+        }
+        self.set_user_var_value(var, def_site.address, value)
+    }
+
+    /// Sets `value` as the user value of `var` at every one of its definitions, e.g. to assert a
+    /// pointer assigned in a loop is never null. Returns the number of values set.
+    ///
+    /// The core stores user values by definition address, so definitions sharing an address are
+    /// counted once. A variable without definitions sets nothing and returns `Ok(0)`.
+    pub fn set_user_var_value_all_defs(
+        &self,
+        var: &Variable,
+        value: PossibleValueSet,
+    ) -> Result<usize, SetVarValueError> {
+        let mut def_addrs: Vec<u64> = vec![];
+        for def in self.get_var_definitions(var) {
+            if !def_addrs.contains(&def.address) {
+                def_addrs.push(def.address);
+            }
+        }
+        let count = def_addrs.len();
+        self.set_user_var_values(
+            def_addrs
+                .into_iter()
+                .map(|addr| (*var, addr, value.clone())),
+        )?;
+        Ok(count)
+    }
+
+    /// Sets multiple user variable values at once, see [MediumLevelILFunction::set_user_var_value].
+    ///
+    /// Analysis is held while the values are applied, so the function is only reanalyzed once
+    /// all of them are set instead of once per value. If the caller already holds analysis the
+    /// hold is left in place and no update is triggered.
+    ///
+    /// Returns `Err` as soon as a value has no definition at the given address. Values applied
+    /// before that point are kept.
+    pub fn set_user_var_values<I>(&self, values: I) -> Result<(), SetVarValueError>
+    where
+        I: IntoIterator<Item = (Variable, u64, PossibleValueSet)>,
+    {
+        let _hold = AnalysisHold::new(self.view());
+        values
+            .into_iter()
+            .try_for_each(|(var, addr, value)| self.set_user_var_value(&var, addr, value))
+    }
+
     /// Clears a previously defined user variable value.
     ///
     /// * `var` - Variable for which the value was informed
@@ -236,24 +1352,127 @@ impl MediumLevelILFunction {
         Ok(())
     }
 
+    /// Like [MediumLevelILFunction::clear_user_var_value], but takes the defining instruction
+    /// itself instead of its address, see [MediumLevelILFunction::set_user_var_value_at_def].
+    ///
+    /// Returns `Err` if `def_site` is not a definition of `var`.
+    pub fn clear_user_var_value_at_def(
+        &self,
+        var: &Variable,
+        def_site: &MediumLevelILInstruction,
+    ) -> Result<(), ()> {
+        if !self
+            .get_var_definitions(var)
+            .any(|def| def.index == def_site.index)
+        {
+            return Err(());
+        }
+        self.clear_user_var_value(var, def_site.address)
+    }
+
     /// Returns a map of current defined user variable values.
     /// Returns a Map of user current defined user variable values and their definition sites.
-    pub fn user_var_values(&self) -> UserVariableValues {
+    ///
+    /// Returns `Err` if the core could not provide the values.
+    pub fn user_var_values(&self) -> Result<UserVariableValues, ()> {
         let mut count = 0;
         let function = self.get_function();
         let var_values = unsafe { BNGetAllUserVariableValues(function.handle, &mut count) };
-        assert!(!var_values.is_null());
-        UserVariableValues {
-            vars: core::ptr::slice_from_raw_parts(var_values, count),
+        if var_values.is_null() {
+            return Err(());
         }
+        Ok(UserVariableValues {
+            vars: core::ptr::slice_from_raw_parts(var_values, count),
+        })
+    }
+
+    /// Returns the definition site addresses and values of the user defined values of `var`.
+    ///
+    /// Returns `Err` if the core could not provide the values.
+    pub fn user_var_values_for(
+        &self,
+        var: &Variable,
+    ) -> Result<impl Iterator<Item = (u64, PossibleValueSet)>, ()> {
+        let values: Vec<_> = self
+            .user_var_values()?
+            .values_from_variable(*var)
+            .map(|(def_site, value)| (def_site.address, value))
+            .collect();
+        Ok(values.into_iter())
     }
 
     /// Clear all user defined variable values.
-    pub fn clear_user_var_values(&self) -> Result<(), ()> {
-        for (var, arch_and_addr, _value) in self.user_var_values().all() {
-            self.clear_user_var_value(&var, arch_and_addr.address)?;
+    ///
+    /// Returns the number of values cleared. A value that can not be cleared does not stop the
+    /// others from being cleared, but makes the whole call return `Err` once all of them have
+    /// been attempted.
+    pub fn clear_user_var_values(&self) -> Result<usize, ()> {
+        let mut cleared = 0;
+        let mut result = Ok(());
+        for (var, arch_and_addr, _value) in self.user_var_values()?.all() {
+            match self.clear_user_var_value(&var, arch_and_addr.address) {
+                Ok(()) => cleared += 1,
+                Err(()) => result = Err(()),
+            }
         }
-        Ok(())
+        result.map(|()| cleared)
+    }
+
+    /// Replays the user stack variables and user variable values of `other` onto this function,
+    /// for example to carry annotations over to another copy of the same code.
+    ///
+    /// `offset_map` translates a location in `other` into the matching location in this
+    /// function. It is called with the frame offsets of stack variables, and with the offset
+    /// from the start of `other` of each definition site. Annotations for which it returns
+    /// `None` are skipped.
+    ///
+    /// Returns `Err` if the values of `other` can not be read, or if a translated definition
+    /// site does not define the variable in this function. Annotations applied before that
+    /// point are kept.
+    pub fn copy_user_var_annotations_from<F>(
+        &self,
+        other: &MediumLevelILFunction,
+        offset_map: F,
+    ) -> Result<(), ()>
+    where
+        F: Fn(i64) -> Option<i64>,
+    {
+        let function = self.get_function();
+        let other_function = other.get_function();
+
+        for var in other_function.stack_layout().iter() {
+            if var.auto_defined() || !var.has_type() {
+                continue;
+            }
+            let Some(offset) = offset_map(var.var().storage) else {
+                continue;
+            };
+            let var_type = var.var_type();
+            self.create_user_stack_var(
+                offset,
+                Conf::new(&*var_type, var.type_confidence()),
+                var.name(),
+            );
+        }
+
+        let map_var = |var: Variable| match var.t {
+            BNVariableSourceType::StackVariableSourceType => Some(Variable {
+                storage: offset_map(var.storage)?,
+                ..var
+            }),
+            _ => Some(var),
+        };
+        let other_start = other_function.start();
+        let values: Vec<_> = other
+            .user_var_values()?
+            .all()
+            .filter_map(|(var, def_site, value)| {
+                let offset = offset_map(def_site.address.wrapping_sub(other_start) as i64)?;
+                let addr = function.start().wrapping_add_signed(offset);
+                Some((map_var(var)?, addr, value))
+            })
+            .collect();
+        self.set_user_var_values(values).map_err(|_| ())
     }
 
     pub fn create_auto_stack_var<'a, T: Into<Conf<&'a Type>>, S: BnStrCompatible>(
@@ -280,6 +1499,11 @@ impl MediumLevelILFunction {
         unsafe { BNDeleteAutoStackVariable(self.get_function().handle, offset) }
     }
 
+    /// Creates an auto variable for `var`.
+    ///
+    /// The confidence of `var_type` is stored as given, so passing e.g. `Conf::new(&ty, 128)`
+    /// leaves room for the user to override a guessed type. A bare `&Type` is stored with
+    /// [crate::types::max_confidence].
     pub fn create_auto_var<'a, S: BnStrCompatible, C: Into<Conf<&'a Type>>>(
         &self,
         var: &Variable,
@@ -305,9 +1529,9 @@ impl MediumLevelILFunction {
     /// Returns a list of ILReferenceSource objects (IL xrefs or cross-references)
     /// that reference the given variable. The variable is a local variable that can be either on the stack,
     /// in a register, or in a flag.
-    /// This function is related to get_hlil_var_refs(), which returns variable references collected
-    /// from HLIL. The two can be different in several cases, e.g., multiple variables in MLIL can be merged
-    /// into a single variable in HLIL.
+    /// This function is related to [MediumLevelILFunction::hlil_var_refs], which returns variable
+    /// references collected from HLIL. The two can be different in several cases, e.g., multiple
+    /// variables in MLIL can be merged into a single variable in HLIL.
     ///
     /// * `var` - Variable for which to query the xref
     ///
@@ -317,9 +1541,13 @@ impl MediumLevelILFunction {
     /// # use binaryninja::types::Variable;
     /// # let mlil_fun: MediumLevelILFunction = todo!();
     /// # let mlil_var: Variable = todo!();
-    /// let instr = mlil_fun.var_refs(&mlil_var).get(0).expr();
+    /// let var_refs = mlil_fun.var_refs(&mlil_var).unwrap();
+    /// let instr = var_refs.get(0).expr();
+    /// for xref in &var_refs {
+    ///     println!("{:#x}", xref.addr());
+    /// }
     /// ```
-    pub fn var_refs(&self, var: &Variable) -> Array<ILReferenceSource> {
+    pub fn var_refs(&self, var: &Variable) -> Result<Array<ILReferenceSource>, ()> {
         let mut count = 0;
         let refs = unsafe {
             BNGetMediumLevelILVariableReferences(
@@ -328,8 +1556,30 @@ impl MediumLevelILFunction {
                 &mut count,
             )
         };
-        assert!(!refs.is_null());
-        unsafe { Array::new(refs, count, self.to_owned()) }
+        if refs.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(refs, count, self.to_owned()) })
+    }
+
+    /// Returns the references to `var` collected from the HLIL of the function, see
+    /// [MediumLevelILFunction::var_refs] for how they can differ from the MLIL references. Use
+    /// [ILReferenceSource::hlil_expr] to get the referencing expressions.
+    ///
+    /// * `var` - Variable for which to query the xref
+    pub fn hlil_var_refs(&self, var: &Variable) -> Result<Array<ILReferenceSource>, ()> {
+        let mut count = 0;
+        let refs = unsafe {
+            BNGetHighLevelILVariableReferences(
+                self.get_function().handle,
+                &mut var.raw(),
+                &mut count,
+            )
+        };
+        if refs.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(refs, count, self.to_owned()) })
     }
 
     /// Returns a list of variables referenced by code in the function ``func``,
@@ -348,7 +1598,7 @@ impl MediumLevelILFunction {
         addr: u64,
         length: Option<u64>,
         arch: Option<CoreArchitecture>,
-    ) -> Array<VariableReferenceSource> {
+    ) -> Result<Array<VariableReferenceSource>, ()> {
         let function = self.get_function();
         let arch = arch.unwrap_or_else(|| function.arch());
         let mut count = 0;
@@ -368,8 +1618,31 @@ impl MediumLevelILFunction {
                 BNGetMediumLevelILVariableReferencesFrom(function.handle, arch.0, addr, &mut count)
             }
         };
-        assert!(!refs.is_null());
-        unsafe { Array::new(refs, count, self.to_owned()) }
+        if refs.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Array::new(refs, count, self.to_owned()) })
+    }
+}
+
+impl ILFunction for MediumLevelILFunction {
+    type Instruction<'a> = MediumLevelILInstruction;
+    type Block<'a> = MediumLevelILBlock;
+
+    fn instruction_count(&self) -> usize {
+        self.instruction_count()
+    }
+
+    fn instruction_at_index(&self, instr_idx: usize) -> MediumLevelILInstruction {
+        self.instruction_from_instruction_idx(instr_idx)
+    }
+
+    fn basic_blocks(&self) -> Result<Array<BasicBlock<MediumLevelILBlock>>, ()> {
+        self.basic_blocks()
+    }
+
+    fn get_function(&self) -> Ref<Function> {
+        self.get_function()
     }
 }
 
@@ -399,16 +1672,25 @@ impl core::fmt::Debug for MediumLevelILFunction {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct MediumLevelILInstructionList<'a> {
     mlil: &'a MediumLevelILFunction,
     ptr: *mut usize,
     instr_idxs: core::slice::Iter<'a, usize>,
 }
 
+impl MediumLevelILInstructionList<'_> {
+    /// Instruction indices not yet yielded by this iterator.
+    pub fn as_slice(&self) -> &[usize] {
+        self.instr_idxs.as_slice()
+    }
+}
+
 impl Drop for MediumLevelILInstructionList<'_> {
     fn drop(&mut self) {
-        unsafe { BNFreeILInstructionList(self.ptr) };
+        if !self.ptr.is_null() {
+            unsafe { BNFreeILInstructionList(self.ptr) };
+        }
     }
 }
 
@@ -433,11 +1715,279 @@ impl DoubleEndedIterator for MediumLevelILInstructionList<'_> {
 impl ExactSizeIterator for MediumLevelILInstructionList<'_> {}
 impl core::iter::FusedIterator for MediumLevelILInstructionList<'_> {}
 
+/// Value of a constant expression, see [MediumLevelILFunction::constants].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MediumLevelILConstant {
+    /// `MLIL_CONST`, sign extended from the size of the expression.
+    Int(i64),
+    /// `MLIL_CONST_PTR` or `MLIL_EXTERN_PTR` address.
+    Pointer(u64),
+}
+
+/// Sign extends the low `size` bytes of `value`.
+fn sign_extend(value: u64, size: usize) -> i64 {
+    match size {
+        1..=7 => {
+            let shift = 64 - size * 8;
+            ((value << shift) as i64) >> shift
+        }
+        _ => value as i64,
+    }
+}
+
+/// Holds analysis of a view while alive. On drop the hold state the view had before is
+/// restored, and analysis is only resumed and updated if the view was not already held.
+struct AnalysisHold {
+    view: Ref<BinaryView>,
+    was_held: bool,
+}
+
+impl AnalysisHold {
+    fn new(view: Ref<BinaryView>) -> Self {
+        let was_held = view.analysis_progress().state == AnalysisState::HoldState;
+        if !was_held {
+            view.set_analysis_hold(true);
+        }
+        Self { view, was_held }
+    }
+}
+
+impl Drop for AnalysisHold {
+    fn drop(&mut self) {
+        if !self.was_held {
+            self.view.set_analysis_hold(false);
+            self.view.update_analysis();
+        }
+    }
+}
+
+/// Address ranges of a function, see [MediumLevelILFunction::address_set].
+///
+/// The ranges are sorted and merged on construction, so each lookup is a binary search. The
+/// set does not follow later changes to the function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionAddressSet {
+    ranges: Vec<Range<u64>>,
+}
+
+impl FunctionAddressSet {
+    fn new(mut ranges: Vec<Range<u64>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_unstable_by_key(|range| range.start);
+        let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// Whether `addr` lies within one of the ranges.
+    pub fn contains(&self, addr: u64) -> bool {
+        let idx = self.ranges.partition_point(|range| range.end <= addr);
+        self.ranges
+            .get(idx)
+            .map_or(false, |range| range.start <= addr)
+    }
+
+    /// The sorted, non-overlapping ranges of the set.
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+}
+
+/// 64-bit FNV-1a, used where a hash must not depend on the Rust version or build, unlike
+/// [std::collections::hash_map::DefaultHasher]. Integers are hashed as little-endian bytes.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes())
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Reverse postorder of the graph with the given successor lists, see
+/// [MediumLevelILFunction::basic_blocks_rpo]. Node 0 is the entry.
+fn reverse_postorder(successors: &[Vec<usize>]) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut order = Vec::with_capacity(successors.len());
+    for root in 0..successors.len() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut postorder = vec![];
+        // Explicit stack of (node, next successor to visit), since obfuscated code can produce
+        // CFGs deep enough to overflow a recursive walk.
+        let mut stack = vec![(root, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            if let Some(&succ) = successors[*node].get(*next) {
+                *next += 1;
+                if !visited[succ] {
+                    visited[succ] = true;
+                    stack.push((succ, 0));
+                }
+            } else {
+                postorder.push(*node);
+                stack.pop();
+            }
+        }
+        // Reversed per root, so the entry comes first and each unreachable region only
+        // follows the blocks reachable before it.
+        order.extend(postorder.into_iter().rev());
+    }
+    order
+}
+
+/// Reason a user variable value could not be set, see
+/// [MediumLevelILFunction::set_user_var_value].
+///
+/// The core accepts every value for an existing definition site, so all errors are detected
+/// before the value is handed to it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SetVarValueError {
+    /// The variable has no definition at the given address.
+    NoDefinitionAtAddress,
+    /// The given instruction is not a definition of the variable.
+    NotADefinition,
+}
+
+/// Overview of a [MediumLevelILFunction], see [MediumLevelILFunction::summary].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MediumLevelILFunctionSummary {
+    pub instruction_count: usize,
+    pub basic_block_count: usize,
+    /// Number of calls, syscalls and tail calls.
+    pub call_count: usize,
+    pub variable_count: usize,
+    /// Whether the function is in SSA form or its SSA form is available.
+    pub has_ssa: bool,
+}
+
+pub struct MediumLevelILInstructionIter {
+    function: Ref<MediumLevelILFunction>,
+    range: Range<usize>,
+}
+
+impl Iterator for MediumLevelILInstructionIter {
+    type Item = MediumLevelILInstruction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|i| self.function.instruction_from_instruction_idx(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for MediumLevelILInstructionIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|i| self.function.instruction_from_instruction_idx(i))
+    }
+}
+
+impl ExactSizeIterator for MediumLevelILInstructionIter {}
+impl core::iter::FusedIterator for MediumLevelILInstructionIter {}
+
 /////////////////////////
 // FunctionGraphType
 
 pub type FunctionGraphType = binaryninjacore_sys::BNFunctionGraphType;
 
+/////////////////////////
+// ILFunctionKind
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum ILFunctionKind {
+    Invalid,
+    Disassembly,
+    LowLevelIL,
+    LiftedIL,
+    LowLevelILSSA,
+    MediumLevelIL,
+    MediumLevelILSSA,
+    MappedMediumLevelIL,
+    MappedMediumLevelILSSA,
+    HighLevelIL,
+    HighLevelILSSA,
+    HighLevelLanguageRepresentation,
+}
+
+impl From<FunctionGraphType> for ILFunctionKind {
+    fn from(graph_type: FunctionGraphType) -> Self {
+        use BNFunctionGraphType::*;
+
+        match graph_type {
+            InvalidILViewType => ILFunctionKind::Invalid,
+            NormalFunctionGraph => ILFunctionKind::Disassembly,
+            LowLevelILFunctionGraph => ILFunctionKind::LowLevelIL,
+            LiftedILFunctionGraph => ILFunctionKind::LiftedIL,
+            LowLevelILSSAFormFunctionGraph => ILFunctionKind::LowLevelILSSA,
+            MediumLevelILFunctionGraph => ILFunctionKind::MediumLevelIL,
+            MediumLevelILSSAFormFunctionGraph => ILFunctionKind::MediumLevelILSSA,
+            MappedMediumLevelILFunctionGraph => ILFunctionKind::MappedMediumLevelIL,
+            MappedMediumLevelILSSAFormFunctionGraph => ILFunctionKind::MappedMediumLevelILSSA,
+            HighLevelILFunctionGraph => ILFunctionKind::HighLevelIL,
+            HighLevelILSSAFormFunctionGraph => ILFunctionKind::HighLevelILSSA,
+            HighLevelLanguageRepresentationFunctionGraph => {
+                ILFunctionKind::HighLevelLanguageRepresentation
+            }
+        }
+    }
+}
+
+impl From<ILFunctionKind> for FunctionGraphType {
+    fn from(kind: ILFunctionKind) -> Self {
+        use BNFunctionGraphType::*;
+
+        match kind {
+            ILFunctionKind::Invalid => InvalidILViewType,
+            ILFunctionKind::Disassembly => NormalFunctionGraph,
+            ILFunctionKind::LowLevelIL => LowLevelILFunctionGraph,
+            ILFunctionKind::LiftedIL => LiftedILFunctionGraph,
+            ILFunctionKind::LowLevelILSSA => LowLevelILSSAFormFunctionGraph,
+            ILFunctionKind::MediumLevelIL => MediumLevelILFunctionGraph,
+            ILFunctionKind::MediumLevelILSSA => MediumLevelILSSAFormFunctionGraph,
+            ILFunctionKind::MappedMediumLevelIL => MappedMediumLevelILFunctionGraph,
+            ILFunctionKind::MappedMediumLevelILSSA => MappedMediumLevelILSSAFormFunctionGraph,
+            ILFunctionKind::HighLevelIL => HighLevelILFunctionGraph,
+            ILFunctionKind::HighLevelILSSA => HighLevelILSSAFormFunctionGraph,
+            ILFunctionKind::HighLevelLanguageRepresentation => {
+                HighLevelLanguageRepresentationFunctionGraph
+            }
+        }
+    }
+}
+
 /////////////////////////
 // ILReferenceSource
 
@@ -446,7 +1996,7 @@ pub struct ILReferenceSource {
     _func: Ref<Function>,
     _arch: CoreArchitecture,
     addr: u64,
-    type_: FunctionGraphType,
+    type_: ILFunctionKind,
     expr_id: usize,
 }
 
@@ -457,19 +2007,30 @@ impl ILReferenceSource {
             _func: Function::from_raw(value.func),
             _arch: CoreArchitecture::from_raw(value.arch),
             addr: value.addr,
-            type_: value.type_,
+            type_: value.type_.into(),
             expr_id: value.exprId,
         }
     }
     pub fn addr(&self) -> u64 {
         self.addr
     }
-    pub fn graph_type(&self) -> FunctionGraphType {
+    pub fn graph_type(&self) -> ILFunctionKind {
         self.type_
     }
+    /// The referencing MLIL expression. Only meaningful for references collected from MLIL,
+    /// see [ILReferenceSource::hlil_expr] for references collected from HLIL.
     pub fn expr(&self) -> MediumLevelILInstruction {
         self.mlil.instruction_from_idx(self.expr_id)
     }
+    /// The referencing HLIL expression, or `None` if the reference was not collected from HLIL
+    /// or the HLIL of the function is not available.
+    pub fn hlil_expr(&self) -> Option<HighLevelILInstruction> {
+        if self.type_ != ILFunctionKind::HighLevelIL {
+            return None;
+        }
+        let hlil = self.mlil.high_level_il(true)?;
+        Some(hlil.instruction_from_idx(self.expr_id))
+    }
 }
 
 impl CoreArrayProvider for ILReferenceSource {
@@ -501,6 +2062,20 @@ impl VariableReferenceSource {
     pub fn source(&self) -> &ILReferenceSource {
         &self.source
     }
+
+    /// Whether the referencing expression writes the variable, i.e. the variable is the
+    /// destination of an assignment or an output of a call or intrinsic. A use of the variable
+    /// as an operand of such an expression, like `x` in `y = x` or `f(x)`, is not a write.
+    pub fn is_write(&self) -> bool {
+        self.source.expr().written_vars().contains(&self.var)
+    }
+
+    /// Whether the referencing expression reads the variable, i.e. the variable appears as an
+    /// operand. An expression can both read and write a variable, e.g. `x = x + 1`, or neither
+    /// when the core reports a reference through an operation these helpers do not decode.
+    pub fn is_read(&self) -> bool {
+        self.source.expr().read_vars().contains(&self.var)
+    }
 }
 
 impl CoreArrayProvider for VariableReferenceSource {
@@ -520,3 +2095,84 @@ unsafe impl CoreArrayProviderInner for VariableReferenceSource {
         }
     }
 }
+
+impl Array<VariableReferenceSource> {
+    /// Groups the references by the variable they refer to, keeping the order of the references
+    /// of each variable.
+    pub fn into_var_map(self) -> HashMap<Variable, Vec<ILReferenceSource>> {
+        let mut map: HashMap<Variable, Vec<ILReferenceSource>> = HashMap::new();
+        for var_ref in self.iter() {
+            map.entry(var_ref.var).or_default().push(var_ref.source);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reverse_postorder, sign_extend, Fnv1a, FunctionAddressSet};
+
+    #[test]
+    fn sign_extend_by_size() {
+        assert_eq!(sign_extend(0xff, 1), -1);
+        assert_eq!(sign_extend(0x7f, 1), 0x7f);
+        assert_eq!(sign_extend(0xffff_fffe, 4), -2);
+        assert_eq!(sign_extend(0x8000_0000, 8), 0x8000_0000);
+        assert_eq!(sign_extend(u64::MAX, 8), -1);
+    }
+
+    #[test]
+    fn address_set_merges_and_looks_up() {
+        let set = FunctionAddressSet::new(vec![0x20..0x30, 0x10..0x18, 0x18..0x1c, 0x40..0x40]);
+        assert_eq!(set.ranges(), &[0x10..0x1c, 0x20..0x30]);
+        assert!(set.contains(0x10));
+        assert!(set.contains(0x1b));
+        assert!(!set.contains(0x1c));
+        assert!(!set.contains(0x0f));
+        assert!(set.contains(0x2f));
+        assert!(!set.contains(0x30));
+        assert!(!set.contains(0x40));
+    }
+
+    #[test]
+    fn fnv1a_reference_vectors() {
+        assert_eq!(Fnv1a::new().finish(), 0xcbf2_9ce4_8422_2325);
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.finish(), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn fnv1a_integers_are_little_endian() {
+        let mut from_int = Fnv1a::new();
+        from_int.write_u32(0x0403_0201);
+        let mut from_bytes = Fnv1a::new();
+        from_bytes.write(&[1, 2, 3, 4]);
+        assert_eq!(from_int.finish(), from_bytes.finish());
+    }
+
+    #[test]
+    fn rpo_diamond() {
+        let order = reverse_postorder(&[vec![1, 2], vec![3], vec![3], vec![]]);
+        assert_eq!(order[0], 0);
+        assert_eq!(order[3], 3);
+    }
+
+    #[test]
+    fn rpo_disconnected_block_follows_entry_region() {
+        // 0 -> 1 -> 2 with 3 -> 1 unreachable from the entry.
+        let order = reverse_postorder(&[vec![1], vec![2], vec![], vec![1]]);
+        assert_eq!(order, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn rpo_irreducible_loop() {
+        // Two entries into the loop 1 <-> 2.
+        let order = reverse_postorder(&[vec![1, 2], vec![2], vec![1]]);
+        assert_eq!(order.len(), 3);
+        assert_eq!(order[0], 0);
+    }
+}
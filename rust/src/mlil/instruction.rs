@@ -1,18 +1,44 @@
+use std::collections::HashSet;
+
+use binaryninjacore_sys::BNFreeHighLevelILFunction;
+use binaryninjacore_sys::BNFreeILInstructionList;
+use binaryninjacore_sys::BNFreeInstructionText;
+use binaryninjacore_sys::BNFreeLowLevelILFunction;
+use binaryninjacore_sys::BNFreePossibleValueSet;
 use binaryninjacore_sys::BNGetDefaultIndexForMediumLevelILVariableDefinition;
+use binaryninjacore_sys::BNGetHighLevelILExprCount;
+use binaryninjacore_sys::BNGetHighLevelILExprIndex;
+use binaryninjacore_sys::BNGetHighLevelILForMediumLevelIL;
+use binaryninjacore_sys::BNGetLowLevelILExprCount;
+use binaryninjacore_sys::BNGetLowLevelILExprIndex;
+use binaryninjacore_sys::BNGetLowLevelILForMediumLevelIL;
 use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+use binaryninjacore_sys::BNGetMediumLevelILExprText;
+use binaryninjacore_sys::BNGetMediumLevelILInstructionForExpr;
+use binaryninjacore_sys::BNGetMediumLevelILPossibleExprValues;
+use binaryninjacore_sys::BNGetMediumLevelILSSAVarVersionAtILInstruction;
+use binaryninjacore_sys::BNInstructionTextToken;
+use binaryninjacore_sys::BNInstructionTextTokenType;
 use binaryninjacore_sys::BNMediumLevelILInstruction;
 use binaryninjacore_sys::BNMediumLevelILOperation;
 
+use crate::architecture::Architecture;
+use crate::binaryview::BinaryViewExt;
+use crate::disassembly::InstructionTextToken;
+use crate::function::Location;
 use crate::operand_iter::OperandIter;
-use crate::rc::Ref;
+use crate::rc::{CoreArrayProvider, CoreArrayProviderInner, Ref};
 use crate::types::{
-    ConstantData, ILIntrinsic, RegisterValue, RegisterValueType, SSAVariable, Variable,
+    ConstantData, ILIntrinsic, PossibleValueSet, RegisterValue, RegisterValueType, SSAVariable,
+    Variable,
 };
 
 use super::lift::*;
 use super::operation::*;
 use super::MediumLevelILFunction;
 
+pub use binaryninjacore_sys::BNMediumLevelILOperation as MediumLevelILOperation;
+
 #[derive(Clone)]
 pub struct MediumLevelILInstruction {
     pub function: Ref<MediumLevelILFunction>,
@@ -167,6 +193,21 @@ impl core::fmt::Debug for MediumLevelILInstruction {
     }
 }
 
+impl CoreArrayProvider for MediumLevelILInstruction {
+    type Raw = usize;
+    type Context = Ref<MediumLevelILFunction>;
+    type Wrapped<'a> = Self;
+}
+
+unsafe impl CoreArrayProviderInner for MediumLevelILInstruction {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeILInstructionList(raw)
+    }
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped<'a> {
+        context.instruction_from_instruction_idx(*raw)
+    }
+}
+
 impl MediumLevelILInstruction {
     pub(crate) fn new(function: Ref<MediumLevelILFunction>, index: usize) -> Self {
         let op = unsafe { BNGetMediumLevelILByIndex(function.handle, index) };
@@ -1050,6 +1091,403 @@ impl MediumLevelILInstruction {
         Variable::new(var.t, new_index, var.storage)
     }
 
+    /// Calls `f` on every sub-expression of this expression in pre-order, not including the
+    /// expression itself. List operands such as call parameters are visited in order.
+    pub fn visit_operands<F: FnMut(&MediumLevelILInstruction)>(&self, mut f: F) {
+        self.descendants().for_each(|expr| f(&expr))
+    }
+
+    /// Iterates every sub-expression of this expression in pre-order, not including the
+    /// expression itself. See [Self::visit_operands].
+    pub fn descendants(&self) -> impl Iterator<Item = MediumLevelILInstruction> {
+        fn collect(instr: &MediumLevelILLiftedInstruction, indices: &mut Vec<usize>) {
+            for (_, operand) in instr.operands() {
+                match operand {
+                    MediumLevelILLiftedOperand::Expr(expr) => {
+                        indices.push(expr.index);
+                        collect(&expr, indices);
+                    }
+                    MediumLevelILLiftedOperand::ExprList(list) => {
+                        for expr in &list {
+                            indices.push(expr.index);
+                            collect(expr, indices);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut indices = vec![];
+        collect(&self.lift(), &mut indices);
+        let function = self.function.clone();
+        indices
+            .into_iter()
+            .map(move |expr_idx| MediumLevelILInstruction::new(function.clone(), expr_idx))
+    }
+
+    /// Addresses of constant pointers referenced anywhere in this expression, such as the global
+    /// read by a load from a constant address. For computed addresses like `base + index` only
+    /// the constant `base` is reported. Each address is listed once, in operand order.
+    pub fn data_refs(&self) -> Vec<u64> {
+        use MediumLevelILInstructionKind::*;
+        let mut refs = vec![];
+        for instr in core::iter::once(self.clone()).chain(self.descendants()) {
+            let addr = match instr.kind {
+                ConstPtr(op) | Import(op) => op.constant,
+                ExternPtr(op) => op.constant,
+                _ => continue,
+            };
+            if !refs.contains(&addr) {
+                refs.push(addr);
+            }
+        }
+        refs
+    }
+
+    /// Variables receiving the outputs of this call, syscall or tail call, or `None` for any
+    /// other operation. For the SSA forms the non-SSA variable of each output is returned.
+    pub fn call_output_vars(&self) -> Option<Vec<Variable>> {
+        use MediumLevelILInstructionKind::*;
+        let function = &*self.function;
+        let vars = match self.kind {
+            Call(op) | Tailcall(op) => OperandIter::new(function, op.first_output, op.num_outputs)
+                .vars()
+                .collect(),
+            Syscall(op) => OperandIter::new(function, op.first_output, op.num_outputs)
+                .vars()
+                .collect(),
+            CallUntyped(op) | TailcallUntyped(op) => get_call_output(function, op.output).collect(),
+            SyscallUntyped(op) => get_call_output(function, op.output).collect(),
+            CallSsa(op) | TailcallSsa(op) => get_call_output_ssa(function, op.output)
+                .map(|ssa_var| ssa_var.variable)
+                .collect(),
+            CallUntypedSsa(op) | TailcallUntypedSsa(op) => get_call_output_ssa(function, op.output)
+                .map(|ssa_var| ssa_var.variable)
+                .collect(),
+            SyscallSsa(op) => get_call_output_ssa(function, op.output)
+                .map(|ssa_var| ssa_var.variable)
+                .collect(),
+            SyscallUntypedSsa(op) => get_call_output_ssa(function, op.output)
+                .map(|ssa_var| ssa_var.variable)
+                .collect(),
+            _ => return None,
+        };
+        Some(vars)
+    }
+
+    /// Variables written by this expression itself: assignment destinations, call and intrinsic
+    /// outputs and freed variable slots. For the SSA forms the non-SSA variable is returned.
+    pub(crate) fn written_vars(&self) -> Vec<Variable> {
+        use MediumLevelILInstructionKind::*;
+        let function = &*self.function;
+        match self.kind {
+            SetVar(op) => vec![op.dest],
+            SetVarField(op) => vec![op.dest],
+            FreeVarSlot(op) => vec![op.dest],
+            SetVarSsa(op) => vec![op.dest.variable],
+            SetVarSsaField(op) | SetVarAliasedField(op) => vec![op.dest.variable],
+            SetVarAliased(op) => vec![op.dest.variable],
+            FreeVarSlotSsa(op) => vec![op.dest.variable],
+            VarPhi(op) => vec![op.dest.variable],
+            SetVarSplit(op) => vec![op.high, op.low],
+            SetVarSplitSsa(op) => vec![op.high.variable, op.low.variable],
+            Intrinsic(op) => OperandIter::new(function, op.first_output, op.num_outputs)
+                .vars()
+                .collect(),
+            IntrinsicSsa(op) => OperandIter::new(function, op.first_output, op.num_outputs)
+                .ssa_vars()
+                .map(|ssa_var| ssa_var.variable)
+                .collect(),
+            _ => self.call_output_vars().unwrap_or_default(),
+        }
+    }
+
+    /// Variables read anywhere in this expression or its sub-expressions, including variables
+    /// whose address is taken and the previous value kept by a partial (field) assignment.
+    /// For the SSA forms the non-SSA variable is returned.
+    pub(crate) fn read_vars(&self) -> Vec<Variable> {
+        use MediumLevelILInstructionKind::*;
+        let mut vars = vec![];
+        for expr in core::iter::once(self.clone()).chain(self.descendants()) {
+            match expr.kind {
+                Var(op) | AddressOf(op) => vars.push(op.src),
+                VarField(op) | AddressOfField(op) => vars.push(op.src),
+                VarSsa(op) | VarAliased(op) => vars.push(op.src.variable),
+                VarSsaField(op) | VarAliasedField(op) => vars.push(op.src.variable),
+                VarSplit(op) => vars.extend([op.high, op.low]),
+                VarSplitSsa(op) => vars.extend([op.high.variable, op.low.variable]),
+                SetVarSsaField(op) | SetVarAliasedField(op) => vars.push(op.prev.variable),
+                VarPhi(_) => vars.extend(
+                    expr.phi_sources()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|ssa_var| ssa_var.variable),
+                ),
+                _ => {}
+            }
+        }
+        vars
+    }
+
+    /// Source SSA variables of this `MLIL_VAR_PHI`, or `None` for any other operation.
+    pub fn phi_sources(&self) -> Option<Vec<SSAVariable>> {
+        let MediumLevelILInstructionKind::VarPhi(op) = self.kind else {
+            return None;
+        };
+        Some(
+            OperandIter::new(&*self.function, op.first_operand, op.num_operands)
+                .ssa_vars()
+                .collect(),
+        )
+    }
+
+    /// Destination SSA variable of this `MLIL_VAR_PHI`, or `None` for any other operation.
+    pub fn phi_dest(&self) -> Option<SSAVariable> {
+        match self.kind {
+            MediumLevelILInstructionKind::VarPhi(op) => Some(op.dest),
+            _ => None,
+        }
+    }
+
+    /// SSA variables read by this instruction that are derived from any of `sources`, in operand
+    /// order.
+    ///
+    /// Derivation follows SSA def-use chains: every variable defined by an instruction that uses
+    /// a derived variable is itself derived. This over-approximates, e.g. all outputs of a call
+    /// are derived from any of its arguments, but does not track values through memory.
+    ///
+    /// The function of this instruction must be in SSA form, see [MediumLevelILFunction::ssa_form].
+    pub fn operands_reachable_from(&self, sources: &[SSAVariable]) -> Vec<SSAVariable> {
+        let function = &*self.function;
+        let defines = |instr: &MediumLevelILInstruction, var: &SSAVariable| {
+            function
+                .get_ssa_var_definition(var)
+                .map_or(false, |def| def.instr_index() == instr.instr_index())
+        };
+
+        let mut derived: HashSet<SSAVariable> = sources.iter().copied().collect();
+        let mut worklist = sources.to_vec();
+        while let Some(var) = worklist.pop() {
+            let Ok(uses) = function.get_ssa_var_uses(&var) else {
+                continue;
+            };
+            for use_instr in uses.iter() {
+                for written in use_instr.ssa_vars() {
+                    if defines(&use_instr, &written) && derived.insert(written) {
+                        worklist.push(written);
+                    }
+                }
+            }
+        }
+
+        let mut reachable = vec![];
+        for var in self.ssa_vars() {
+            if derived.contains(&var) && !defines(self, &var) && !reachable.contains(&var) {
+                reachable.push(var);
+            }
+        }
+        reachable
+    }
+
+    /// Every SSA variable appearing in this expression or its sub-expressions, read or written.
+    fn ssa_vars(&self) -> Vec<SSAVariable> {
+        fn collect(instr: &MediumLevelILLiftedInstruction, vars: &mut Vec<SSAVariable>) {
+            for (_, operand) in instr.operands() {
+                match operand {
+                    MediumLevelILLiftedOperand::VarSsa(var) => vars.push(var),
+                    MediumLevelILLiftedOperand::VarSsaList(list) => vars.extend(list),
+                    MediumLevelILLiftedOperand::Expr(expr) => collect(&expr, vars),
+                    MediumLevelILLiftedOperand::ExprList(list) => {
+                        list.iter().for_each(|expr| collect(expr, vars))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut vars = vec![];
+        collect(&self.lift(), &mut vars);
+        vars
+    }
+
+    /// Targets the core resolved for this `MLIL_JUMP` or `MLIL_JUMP_TO`, e.g. the entries of
+    /// a jump table. A partially resolved jump table yields the targets found so far. Any other
+    /// operation yields no targets.
+    pub fn indirect_branch_targets(&self) -> Vec<u64> {
+        use MediumLevelILInstructionKind::*;
+        if !matches!(self.kind, Jump(_) | JumpTo(_)) {
+            return vec![];
+        }
+        self.function
+            .get_function()
+            .indirect_branches_at(self.address, None)
+            .iter()
+            .map(|branch| branch.dest_addr)
+            .collect()
+    }
+
+    /// Lifts this instruction and copies the result into an owned snapshot that does not
+    /// reference the core. The snapshot is `Send`, so it can be processed on worker threads
+    /// while the core is left alone.
+    pub fn to_operand_snapshot(&self) -> MediumLevelILOperandSnapshot {
+        (&self.lift()).into()
+    }
+
+    /// Gets the version of the non-SSA variable `var` that is live at this instruction.
+    ///
+    /// * `var` - variable to query
+    pub fn get_ssa_var_version(&self, var: &Variable) -> usize {
+        unsafe {
+            BNGetMediumLevelILSSAVarVersionAtILInstruction(
+                self.function.handle,
+                &var.raw(),
+                self.instr_index(),
+            )
+        }
+    }
+
+    /// Returns the set of values the dataflow computed for this expression as a whole, which can
+    /// be narrower than the value sets of its operands, e.g. for the sum of two masked variables.
+    pub fn possible_values(&self) -> PossibleValueSet {
+        let mut raw_values = unsafe {
+            BNGetMediumLevelILPossibleExprValues(
+                self.function.handle,
+                self.index,
+                core::ptr::null_mut(),
+                0,
+            )
+        };
+        let values = unsafe { PossibleValueSet::from_raw(raw_values) };
+        unsafe { BNFreePossibleValueSet(&mut raw_values) };
+        values
+    }
+
+    /// Returns the index of the LLIL expression this expression was generated from, or `None`
+    /// if the expression has no direct mapping into LLIL.
+    pub fn get_llil_expr_index(&self) -> Option<usize> {
+        let llil = unsafe { BNGetLowLevelILForMediumLevelIL(self.function.handle) };
+        if llil.is_null() {
+            return None;
+        }
+        let expr_idx = unsafe { BNGetLowLevelILExprIndex(self.function.handle, self.index) };
+        let expr_count = unsafe { BNGetLowLevelILExprCount(llil) };
+        unsafe { BNFreeLowLevelILFunction(llil) };
+        (expr_idx < expr_count).then_some(expr_idx)
+    }
+
+    /// Returns the index of the HLIL expression generated from this expression, or `None`
+    /// if the expression has no direct mapping into HLIL.
+    pub fn get_hlil_expr_index(&self) -> Option<usize> {
+        let hlil = unsafe { BNGetHighLevelILForMediumLevelIL(self.function.handle) };
+        if hlil.is_null() {
+            return None;
+        }
+        let expr_idx = unsafe { BNGetHighLevelILExprIndex(self.function.handle, self.index) };
+        let expr_count = unsafe { BNGetHighLevelILExprCount(hlil) };
+        unsafe { BNFreeHighLevelILFunction(hlil) };
+        (expr_idx < expr_count).then_some(expr_idx)
+    }
+
+    /// Source location of this instruction. MLIL does not record an architecture per
+    /// instruction, so the architecture of the owning function is used.
+    ///
+    /// Instructions synthesized by analysis carry the address that was current when they were
+    /// generated, which is not necessarily the start of a machine instruction.
+    pub fn location(&self) -> Location {
+        Location {
+            arch: Some(self.function.get_function().arch()),
+            addr: self.address,
+        }
+    }
+
+    /// Renders this expression into text tokens, using the architecture of the owning
+    /// function. Returns `None` if the core could not render the expression.
+    pub fn tokens(&self) -> Option<Vec<InstructionTextToken>> {
+        let mut count = 0;
+        let mut tokens: *mut BNInstructionTextToken = core::ptr::null_mut();
+        unsafe {
+            if BNGetMediumLevelILExprText(
+                self.function.handle,
+                self.function.get_function().arch().0,
+                self.index,
+                &mut tokens,
+                &mut count,
+                core::ptr::null_mut(),
+            ) {
+                let vec = core::slice::from_raw_parts(tokens, count)
+                    .iter()
+                    .map(|token| InstructionTextToken::from_raw(token).to_owned())
+                    .collect();
+                BNFreeInstructionText(tokens, count);
+                Some(vec)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Renders this expression as text like [Self::tokens], naming each variable with `name_fn`.
+    /// Variables for which `name_fn` returns `None` keep the name the core gives them.
+    ///
+    /// Nothing is written to the database, unlike renaming with
+    /// [MediumLevelILFunction::create_user_var]. Returns an empty string if the core could not
+    /// render the expression.
+    pub fn render_with<F: Fn(&Variable) -> Option<String>>(&self, name_fn: F) -> String {
+        let mut text = String::new();
+        for token in self.tokens().unwrap_or_default() {
+            let name = (token.0.type_ == BNInstructionTextTokenType::LocalVariableToken)
+                .then(|| name_fn(&unsafe { Variable::from_identifier(token.0.value) }))
+                .flatten();
+            match name {
+                Some(name) => text.push_str(&name),
+                None => text.push_str(token.text()),
+            }
+        }
+        text
+    }
+
+    /// Operation of this expression, read from the core without decoding or lifting operands.
+    pub fn operation(&self) -> MediumLevelILOperation {
+        get_raw_operation(&self.function, self.index).operation
+    }
+
+    /// Width in bytes of the value this expression operates on, e.g. 4 for a 32-bit addition.
+    /// Same as the `size` field, and unrelated to the length of the machine code, see
+    /// [Self::source_byte_length].
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Length in bytes of the machine instruction at [MediumLevelILInstruction::address] that
+    /// this expression was lifted from, decoded with the architecture of the owning function.
+    /// Returns 0 if the bytes could not be read or decoded.
+    ///
+    /// Unlike [Self::size] this is a property of the source bytes, not of the operation; every
+    /// expression lifted from the same machine instruction reports the same length.
+    pub fn source_byte_length(&self) -> usize {
+        let function = self.function.get_function();
+        let arch = function.arch();
+        let data = function.view().read_vec(self.address, arch.max_instr_len());
+        arch.instruction_info(&data, self.address)
+            .map_or(0, |info| info.len())
+    }
+
+    /// Operation and raw operands of this expression, exactly as the core stores them.
+    ///
+    /// Operands are `u64` rather than `usize` since they hold constants as well as expression
+    /// and operand list indices. Their meaning depends on the operation; unused operands are
+    /// left as the core set them.
+    pub fn raw_operands(&self) -> (MediumLevelILOperation, [u64; 5]) {
+        let op = get_raw_operation(&self.function, self.index);
+        (op.operation, op.operands)
+    }
+
+    /// Index of the instruction containing this expression.
+    pub(crate) fn instr_index(&self) -> usize {
+        unsafe { BNGetMediumLevelILInstructionForExpr(self.function.handle, self.index) }
+    }
+
     fn lift_operand(&self, expr_idx: usize) -> Box<MediumLevelILLiftedInstruction> {
         Box::new(self.function.lifted_instruction_from_idx(expr_idx))
     }
@@ -1,10 +1,10 @@
 use std::collections::BTreeMap;
 
 use crate::rc::Ref;
-use crate::types::{ConstantData, ILIntrinsic, SSAVariable, Variable};
+use crate::types::{ConstantData, ILIntrinsic, RegisterValue, SSAVariable, Variable};
 
 use super::operation::*;
-use super::MediumLevelILFunction;
+use super::{MediumLevelILFunction, MediumLevelILInstruction};
 
 #[derive(Clone)]
 pub enum MediumLevelILLiftedOperand {
@@ -166,6 +166,11 @@ pub enum MediumLevelILLiftedInstructionKind {
 }
 
 impl MediumLevelILLiftedInstruction {
+    /// Returns the live instruction this was lifted from, e.g. to query current dataflow.
+    pub fn unlift(&self) -> MediumLevelILInstruction {
+        MediumLevelILInstruction::new(self.function.clone(), self.index)
+    }
+
     pub fn name(&self) -> &'static str {
         use MediumLevelILLiftedInstructionKind::*;
         match self.kind {
@@ -507,3 +512,67 @@ impl MediumLevelILLiftedInstruction {
         }
     }
 }
+
+/// Owned copy of an instruction and its operands that holds no reference into the core, so it
+/// can be sent to other threads. Sub-expressions are snapshotted recursively.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediumLevelILOperandSnapshot {
+    pub address: u64,
+    pub index: usize,
+    pub size: usize,
+    pub name: &'static str,
+    pub operands: Vec<(&'static str, MediumLevelILSnapshotOperand)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MediumLevelILSnapshotOperand {
+    ConstantData(RegisterValue),
+    Intrinsic(String),
+    Expr(MediumLevelILOperandSnapshot),
+    ExprList(Vec<MediumLevelILOperandSnapshot>),
+    Float(f64),
+    Int(u64),
+    IntList(Vec<u64>),
+    TargetMap(BTreeMap<u64, u64>),
+    Var(Variable),
+    VarList(Vec<Variable>),
+    VarSsa(SSAVariable),
+    VarSsaList(Vec<SSAVariable>),
+}
+
+impl From<&MediumLevelILLiftedInstruction> for MediumLevelILOperandSnapshot {
+    fn from(instr: &MediumLevelILLiftedInstruction) -> Self {
+        use MediumLevelILLiftedOperand as Lifted;
+        use MediumLevelILSnapshotOperand as Operand;
+        let operands = instr
+            .operands()
+            .into_iter()
+            .map(|(name, operand)| {
+                let operand = match operand {
+                    Lifted::ConstantData(data) => Operand::ConstantData(data.value()),
+                    Lifted::Intrinsic(intrinsic) => Operand::Intrinsic(intrinsic.name().to_owned()),
+                    Lifted::Expr(expr) => Operand::Expr((&expr).into()),
+                    Lifted::ExprList(list) => {
+                        Operand::ExprList(list.iter().map(Into::into).collect())
+                    }
+                    Lifted::Float(value) => Operand::Float(value),
+                    Lifted::Int(value) => Operand::Int(value),
+                    Lifted::IntList(list) => Operand::IntList(list),
+                    Lifted::TargetMap(map) => Operand::TargetMap(map),
+                    Lifted::Var(var) => Operand::Var(var),
+                    Lifted::VarList(list) => Operand::VarList(list),
+                    Lifted::VarSsa(var) => Operand::VarSsa(var),
+                    Lifted::VarSsaList(list) => Operand::VarSsaList(list),
+                };
+                (name, operand)
+            })
+            .collect();
+        Self {
+            address: instr.address,
+            index: instr.index,
+            size: instr.size,
+            name: instr.name(),
+            operands,
+        }
+    }
+}
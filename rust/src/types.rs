@@ -1390,6 +1390,44 @@ impl Variable {
             storage: self.storage,
         }
     }
+
+    /// Decodes where this variable lives from its source type and storage.
+    pub fn source(&self) -> VariableSource {
+        match self.t {
+            BNVariableSourceType::StackVariableSourceType => VariableSource::Stack(self.storage),
+            BNVariableSourceType::RegisterVariableSourceType => {
+                VariableSource::Register(self.storage as u32)
+            }
+            BNVariableSourceType::FlagVariableSourceType => {
+                VariableSource::Flag(self.storage as u32)
+            }
+        }
+    }
+}
+
+/// Storage location of a [Variable], see [Variable::source].
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum VariableSource {
+    /// Register with the given register id.
+    Register(u32),
+    /// Stack slot at the given offset.
+    Stack(i64),
+    /// Flag with the given flag id.
+    Flag(u32),
+}
+
+/// Variables are ordered by source type (stack, then register, then flag), then by storage,
+/// i.e. stack offset or register/flag id, then by index.
+impl Ord for Variable {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.t as u32, self.storage, self.index).cmp(&(other.t as u32, other.storage, other.index))
+    }
+}
+
+impl PartialOrd for Variable {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl CoreArrayProvider for Variable {
@@ -1472,8 +1510,15 @@ impl NamedTypedVariable {
         self.type_confidence
     }
 
+    /// The type of the variable. The type is owned by the list this variable was read from, so
+    /// a new reference is taken.
     pub fn var_type(&self) -> Ref<Type> {
-        unsafe { Ref::new(Type::from_raw(self.ty)) }
+        unsafe { Type::ref_from_raw(BNNewTypeReference(self.ty)) }
+    }
+
+    /// Whether the core reported a type for this variable, see [NamedTypedVariable::var_type].
+    pub(crate) fn has_type(&self) -> bool {
+        !self.ty.is_null()
     }
 }
 
@@ -2870,6 +2915,10 @@ impl ConstantData {
     pub(crate) fn new(function: Ref<Function>, value: RegisterValue) -> Self {
         Self { function, value }
     }
+
+    pub fn value(&self) -> RegisterValue {
+        self.value
+    }
 }
 
 // unsafe impl<S: BnStrCompatible> CoreArrayProvider for DataVariableAndName<S> {
@@ -2915,6 +2964,51 @@ impl<T> ValueRange<T> {
     }
 }
 
+impl<T> ValueRange<T> {
+    fn key(&self) -> (u64, u64, u64) {
+        (self.raw.start, self.raw.end, self.raw.step)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "ValueRange")]
+struct ValueRangeRepr {
+    start: u64,
+    end: u64,
+    step: u64,
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for ValueRange<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> result::Result<S::Ok, S::Error> {
+        let (start, end, step) = self.key();
+        ValueRangeRepr { start, end, step }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for ValueRange<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> result::Result<Self, D::Error> {
+        let ValueRangeRepr { start, end, step } = ValueRangeRepr::deserialize(deserializer)?;
+        Ok(Self::from_raw(BNValueRange { start, end, step }))
+    }
+}
+
+impl<T> PartialEq for ValueRange<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<T> Eq for ValueRange<T> {}
+
+impl<T> Hash for ValueRange<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
 impl IntoIterator for ValueRange<u64> {
     type Item = u64;
     type IntoIter = core::iter::StepBy<Range<u64>>;
@@ -2936,6 +3030,11 @@ impl IntoIterator for ValueRange<i64> {
 // PossibleValueSet
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type")
+)]
 pub enum PossibleValueSet {
     UndeterminedValue,
     EntryValue {
@@ -2947,12 +3046,17 @@ pub enum PossibleValueSet {
     ConstantPointerValue {
         value: i64,
     },
-    ExternalPointerValue,
+    ExternalPointerValue {
+        value: i64,
+        offset: i64,
+    },
     StackFrameOffset {
         offset: i64,
     },
     ReturnAddressValue,
-    ImportedAddressValue,
+    ImportedAddressValue {
+        value: i64,
+    },
     SignedRangeValue {
         offset: i64,
         ranges: Vec<ValueRange<i64>>,
@@ -2976,7 +3080,8 @@ pub enum PossibleValueSet {
     },
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConstantDataType {
     Value,
     ZeroExtend,
@@ -2984,6 +3089,94 @@ pub enum ConstantDataType {
     Aggregate,
 }
 
+/// Sets compare by variant and contents. Ranges and lookup table entries are compared
+/// regardless of their order.
+impl PartialEq for PossibleValueSet {
+    fn eq(&self, other: &Self) -> bool {
+        fn sorted<T>(ranges: &[ValueRange<T>]) -> Vec<(u64, u64, u64)> {
+            let mut keys: Vec<_> = ranges.iter().map(ValueRange::key).collect();
+            keys.sort_unstable();
+            keys
+        }
+        fn sorted_tables(tables: &[LookupTableEntry]) -> Vec<(Vec<i64>, i64)> {
+            let mut keys: Vec<_> = tables.iter().map(LookupTableEntry::key).collect();
+            keys.sort_unstable();
+            keys
+        }
+        use PossibleValueSet::*;
+        match (self, other) {
+            (UndeterminedValue, UndeterminedValue) => true,
+            (EntryValue { reg: a }, EntryValue { reg: b }) => a == b,
+            (ConstantValue { value: a }, ConstantValue { value: b }) => a == b,
+            (ConstantPointerValue { value: a }, ConstantPointerValue { value: b }) => a == b,
+            (
+                ExternalPointerValue {
+                    value: a,
+                    offset: a_offset,
+                },
+                ExternalPointerValue {
+                    value: b,
+                    offset: b_offset,
+                },
+            ) => a == b && a_offset == b_offset,
+            (StackFrameOffset { offset: a }, StackFrameOffset { offset: b }) => a == b,
+            (ReturnAddressValue, ReturnAddressValue) => true,
+            (ImportedAddressValue { value: a }, ImportedAddressValue { value: b }) => a == b,
+            (
+                SignedRangeValue {
+                    offset: a_offset,
+                    ranges: a,
+                },
+                SignedRangeValue {
+                    offset: b_offset,
+                    ranges: b,
+                },
+            ) => a_offset == b_offset && sorted(a) == sorted(b),
+            (
+                UnsignedRangeValue {
+                    offset: a_offset,
+                    ranges: a,
+                },
+                UnsignedRangeValue {
+                    offset: b_offset,
+                    ranges: b,
+                },
+            ) => a_offset == b_offset && sorted(a) == sorted(b),
+            (LookupTableValue { tables: a }, LookupTableValue { tables: b }) => {
+                sorted_tables(a) == sorted_tables(b)
+            }
+            (InSetOfValues { values: a }, InSetOfValues { values: b }) => a == b,
+            (NotInSetOfValues { values: a }, NotInSetOfValues { values: b }) => a == b,
+            (
+                ConstantDataValue {
+                    value_type: a_type,
+                    value: a,
+                },
+                ConstantDataValue {
+                    value_type: b_type,
+                    value: b,
+                },
+            ) => a_type == b_type && a == b,
+            (UndeterminedValue, _)
+            | (EntryValue { .. }, _)
+            | (ConstantValue { .. }, _)
+            | (ConstantPointerValue { .. }, _)
+            | (ExternalPointerValue { .. }, _)
+            | (StackFrameOffset { .. }, _)
+            | (ReturnAddressValue, _)
+            | (ImportedAddressValue { .. }, _)
+            | (SignedRangeValue { .. }, _)
+            | (UnsignedRangeValue { .. }, _)
+            | (LookupTableValue { .. }, _)
+            | (InSetOfValues { .. }, _)
+            | (NotInSetOfValues { .. }, _)
+            | (ConstantDataValue { .. }, _) => false,
+        }
+    }
+}
+
+impl Eq for PossibleValueSet {}
+
 impl PossibleValueSet {
     pub(crate) unsafe fn from_raw(value: BNPossibleValueSet) -> Self {
         unsafe fn from_range<T>(value: BNPossibleValueSet) -> Vec<ValueRange<T>> {
@@ -3052,9 +3245,12 @@ impl PossibleValueSet {
             InSetOfValues => Self::InSetOfValues {
                 values: from_sets(value),
             },
-            ImportedAddressValue => Self::ImportedAddressValue,
+            ImportedAddressValue => Self::ImportedAddressValue { value: value.value },
             ReturnAddressValue => Self::ReturnAddressValue,
-            ExternalPointerValue => Self::ExternalPointerValue,
+            ExternalPointerValue => Self::ExternalPointerValue {
+                value: value.value,
+                offset: value.offset,
+            },
         }
     }
     pub(crate) fn into_raw(self) -> PossibleValueSetRaw {
@@ -3063,15 +3259,17 @@ impl PossibleValueSet {
         raw.state = self.value_type().into_raw_value();
         // set all other fields
         match self {
-            PossibleValueSet::UndeterminedValue
-            | PossibleValueSet::ExternalPointerValue
-            | PossibleValueSet::ReturnAddressValue
-            | PossibleValueSet::ImportedAddressValue => {}
+            PossibleValueSet::UndeterminedValue | PossibleValueSet::ReturnAddressValue => {}
             PossibleValueSet::EntryValue { reg: value }
             | PossibleValueSet::ConstantValue { value }
             | PossibleValueSet::ConstantPointerValue { value }
             | PossibleValueSet::ConstantDataValue { value, .. }
+            | PossibleValueSet::ImportedAddressValue { value }
             | PossibleValueSet::StackFrameOffset { offset: value } => raw.value = value,
+            PossibleValueSet::ExternalPointerValue { value, offset } => {
+                raw.value = value;
+                raw.offset = offset;
+            }
             PossibleValueSet::NotInSetOfValues { values }
             | PossibleValueSet::InSetOfValues { values } => {
                 let values = Box::leak(values.into_iter().collect());
@@ -3107,10 +3305,10 @@ impl PossibleValueSet {
             PossibleValueSet::EntryValue { .. } => EntryValue,
             PossibleValueSet::ConstantValue { .. } => ConstantValue,
             PossibleValueSet::ConstantPointerValue { .. } => ConstantPointerValue,
-            PossibleValueSet::ExternalPointerValue => ExternalPointerValue,
+            PossibleValueSet::ExternalPointerValue { .. } => ExternalPointerValue,
             PossibleValueSet::StackFrameOffset { .. } => StackFrameOffset,
             PossibleValueSet::ReturnAddressValue => ReturnAddressValue,
-            PossibleValueSet::ImportedAddressValue => ImportedAddressValue,
+            PossibleValueSet::ImportedAddressValue { .. } => ImportedAddressValue,
             PossibleValueSet::SignedRangeValue { .. } => SignedRangeValue,
             PossibleValueSet::UnsignedRangeValue { .. } => UnsignedRangeValue,
             PossibleValueSet::LookupTableValue { .. } => LookupTableValue,
@@ -3183,16 +3381,199 @@ impl Drop for PossibleValueSetRaw {
     }
 }
 
+/// Builds a [PossibleValueSet] piece by piece, checking it before it is handed to the core.
+///
+/// Range, set and lookup table methods can be called repeatedly to add to the set. Mixing
+/// different kinds of values, an empty range step, a range ending before its start or a
+/// lookup table entry without source values makes [PossibleValueSetBuilder::build] fail.
+#[must_use]
+#[derive(Clone, Debug)]
+pub struct PossibleValueSetBuilder {
+    set: Option<PossibleValueSet>,
+    offset: i64,
+    valid: bool,
+}
+
+impl PossibleValueSetBuilder {
+    /// Creates a builder without any values; building it right away fails.
+    pub fn new() -> Self {
+        Self {
+            set: None,
+            offset: 0,
+            valid: true,
+        }
+    }
+
+    fn update<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(Option<PossibleValueSet>) -> Option<PossibleValueSet>,
+    {
+        if self.valid {
+            self.set = f(self.set.take());
+            self.valid = self.set.is_some();
+        }
+        self
+    }
+
+    /// Makes the set a single constant. Fails if anything else was added.
+    pub fn constant(self, value: i64) -> Self {
+        self.update(|set| {
+            set.is_none()
+                .then_some(PossibleValueSet::ConstantValue { value })
+        })
+    }
+
+    /// Makes the set a single constant pointer. Fails if anything else was added.
+    pub fn constant_pointer(self, value: i64) -> Self {
+        self.update(|set| {
+            set.is_none()
+                .then_some(PossibleValueSet::ConstantPointerValue { value })
+        })
+    }
+
+    /// Adds the signed range from `start` to `end` with stride `step`. Fails if `step` is zero, `end`
+    /// is less than `start` or the set is not a signed range set.
+    pub fn signed_range(self, start: i64, end: i64, step: u64) -> Self {
+        let range = (step != 0 && start <= end).then(|| {
+            ValueRange::from_raw(BNValueRange {
+                start: start as u64,
+                end: end as u64,
+                step,
+            })
+        });
+        self.update(|set| match (set, range) {
+            (None, Some(range)) => Some(PossibleValueSet::SignedRangeValue {
+                offset: 0,
+                ranges: vec![range],
+            }),
+            (Some(PossibleValueSet::SignedRangeValue { offset, mut ranges }), Some(range)) => {
+                ranges.push(range);
+                Some(PossibleValueSet::SignedRangeValue { offset, ranges })
+            }
+            _ => None,
+        })
+    }
+
+    /// Adds the unsigned range from `start` to `end` with stride `step`. Fails if `step` is zero, `end`
+    /// is less than `start` or the set is not an unsigned range set.
+    pub fn unsigned_range(self, start: u64, end: u64, step: u64) -> Self {
+        let range = (step != 0 && start <= end)
+            .then(|| ValueRange::from_raw(BNValueRange { start, end, step }));
+        self.update(|set| match (set, range) {
+            (None, Some(range)) => Some(PossibleValueSet::UnsignedRangeValue {
+                offset: 0,
+                ranges: vec![range],
+            }),
+            (Some(PossibleValueSet::UnsignedRangeValue { offset, mut ranges }), Some(range)) => {
+                ranges.push(range);
+                Some(PossibleValueSet::UnsignedRangeValue { offset, ranges })
+            }
+            _ => None,
+        })
+    }
+
+    /// Adds `values` to the values the variable can take. Fails if the set is neither empty nor
+    /// an in-set set, or if no values were added at all.
+    pub fn in_set<I: IntoIterator<Item = i64>>(self, values: I) -> Self {
+        self.update(|set| {
+            let mut set_values = match set {
+                None => HashSet::new(),
+                Some(PossibleValueSet::InSetOfValues { values }) => values,
+                Some(_) => return None,
+            };
+            set_values.extend(values);
+            (!set_values.is_empty())
+                .then_some(PossibleValueSet::InSetOfValues { values: set_values })
+        })
+    }
+
+    /// Adds `values` to the values the variable cannot take. Fails if the set is neither empty
+    /// nor a not-in-set set, or if no values were added at all.
+    pub fn not_in_set<I: IntoIterator<Item = i64>>(self, values: I) -> Self {
+        self.update(|set| {
+            let mut set_values = match set {
+                None => HashSet::new(),
+                Some(PossibleValueSet::NotInSetOfValues { values }) => values,
+                Some(_) => return None,
+            };
+            set_values.extend(values);
+            (!set_values.is_empty())
+                .then_some(PossibleValueSet::NotInSetOfValues { values: set_values })
+        })
+    }
+
+    /// Adds a lookup table entry mapping each of `from_values` to `to_value`. Fails if
+    /// `from_values` is empty or the set is not a lookup table.
+    pub fn lookup_table_entry(self, from_values: &[i64], to_value: i64) -> Self {
+        self.update(|set| {
+            let mut tables = match set {
+                None => vec![],
+                Some(PossibleValueSet::LookupTableValue { tables }) => tables,
+                Some(_) => return None,
+            };
+            if from_values.is_empty() {
+                return None;
+            }
+            tables.push(LookupTableEntry {
+                from_values: from_values.to_vec(),
+                to_value,
+            });
+            Some(PossibleValueSet::LookupTableValue { tables })
+        })
+    }
+
+    /// Sets the offset of a range set, 0 by default. Only range sets have an offset, so a
+    /// nonzero offset on any other set makes [PossibleValueSetBuilder::build] fail.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the built set, or `Err` if nothing was added or the set is malformed.
+    pub fn build(self) -> Result<PossibleValueSet> {
+        let set = self.set.filter(|_| self.valid).ok_or(())?;
+        match set {
+            PossibleValueSet::SignedRangeValue { ranges, .. } => {
+                Ok(PossibleValueSet::SignedRangeValue {
+                    offset: self.offset,
+                    ranges,
+                })
+            }
+            PossibleValueSet::UnsignedRangeValue { ranges, .. } => {
+                Ok(PossibleValueSet::UnsignedRangeValue {
+                    offset: self.offset,
+                    ranges,
+                })
+            }
+            set if self.offset == 0 => Ok(set),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for PossibleValueSetBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /////////////////////////
 // LookupTableEntry
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LookupTableEntry {
     pub from_values: Vec<i64>,
     pub to_value: i64,
 }
 
 impl LookupTableEntry {
+    fn key(&self) -> (Vec<i64>, i64) {
+        let mut from_values = self.from_values.clone();
+        from_values.sort_unstable();
+        (from_values, self.to_value)
+    }
+
     fn into_raw(self) -> LookupTableEntryRaw {
         let from_value = Box::leak(self.from_values.into_boxed_slice());
         LookupTableEntryRaw(BNLookupTableEntry {
@@ -3707,3 +4088,216 @@ unsafe impl CoreArrayProviderInner for UnresolvedIndirectBranches {
         Self(*raw)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Converting a `Conf<&Type>` only copies the handle, so a dangling one is fine here.
+
+    #[test]
+    fn conf_type_converts_with_its_confidence() {
+        let ty = Type {
+            handle: core::ptr::NonNull::dangling().as_ptr(),
+        };
+        let raw: BNTypeWithConfidence = Conf::new(&ty, 128).into();
+        assert_eq!(raw.type_, ty.handle);
+        assert_eq!(raw.confidence, 128);
+    }
+
+    #[test]
+    fn bare_type_converts_with_max_confidence() {
+        let ty = Type {
+            handle: core::ptr::NonNull::dangling().as_ptr(),
+        };
+        let conf: Conf<&Type> = (&ty).into();
+        let raw: BNTypeWithConfidence = conf.into();
+        assert_eq!(raw.confidence, max_confidence());
+    }
+
+    #[test]
+    fn value_sets_compare_ranges_regardless_of_order() {
+        fn range(start: u64, end: u64, step: u64) -> ValueRange<u64> {
+            ValueRange::from_raw(BNValueRange { start, end, step })
+        }
+        let a = PossibleValueSet::UnsignedRangeValue {
+            offset: 0,
+            ranges: vec![range(0, 4, 1), range(8, 16, 2)],
+        };
+        let b = PossibleValueSet::UnsignedRangeValue {
+            offset: 0,
+            ranges: vec![range(8, 16, 2), range(0, 4, 1)],
+        };
+        let c = PossibleValueSet::UnsignedRangeValue {
+            offset: 4,
+            ranges: vec![range(0, 4, 1), range(8, 16, 2)],
+        };
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn value_sets_compare_lookup_tables_regardless_of_order() {
+        fn entry(from_values: &[i64], to_value: i64) -> LookupTableEntry {
+            LookupTableEntry {
+                from_values: from_values.to_vec(),
+                to_value,
+            }
+        }
+        let a = PossibleValueSet::LookupTableValue {
+            tables: vec![entry(&[1, 2], 10), entry(&[3], 20)],
+        };
+        let b = PossibleValueSet::LookupTableValue {
+            tables: vec![entry(&[3], 20), entry(&[2, 1], 10)],
+        };
+        let c = PossibleValueSet::LookupTableValue {
+            tables: vec![entry(&[1, 2], 20), entry(&[3], 10)],
+        };
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn value_sets_of_different_variants_differ() {
+        assert_ne!(
+            PossibleValueSet::ConstantValue { value: 1 },
+            PossibleValueSet::ConstantPointerValue { value: 1 }
+        );
+        assert_ne!(
+            PossibleValueSet::InSetOfValues {
+                values: HashSet::from([1, 2])
+            },
+            PossibleValueSet::NotInSetOfValues {
+                values: HashSet::from([1, 2])
+            }
+        );
+        assert_eq!(
+            PossibleValueSet::ReturnAddressValue,
+            PossibleValueSet::ReturnAddressValue
+        );
+    }
+
+    #[test]
+    fn value_set_builder_rejects_malformed_ranges() {
+        let builder = PossibleValueSetBuilder::new();
+        assert!(builder.clone().signed_range(0, 8, 0).build().is_err());
+        assert!(builder.clone().unsigned_range(0, 8, 0).build().is_err());
+        assert!(builder.clone().signed_range(8, -8, 1).build().is_err());
+        assert!(builder.clone().unsigned_range(8, 0, 1).build().is_err());
+        assert!(builder.signed_range(-8, -8, 1).build().is_ok());
+    }
+
+    #[test]
+    fn value_set_builder_rejects_mixed_variants() {
+        let builder = PossibleValueSetBuilder::new();
+        assert!(builder.clone().constant(1).in_set([2]).build().is_err());
+        assert!(builder.clone().in_set([1]).not_in_set([2]).build().is_err());
+        assert!(builder
+            .clone()
+            .signed_range(0, 4, 1)
+            .unsigned_range(0, 4, 1)
+            .build()
+            .is_err());
+        // A failure sticks even if later calls would be valid on their own.
+        assert!(builder
+            .unsigned_range(4, 0, 1)
+            .unsigned_range(0, 4, 1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn value_set_builder_rejects_empty_sets() {
+        let builder = PossibleValueSetBuilder::new();
+        assert!(builder.clone().build().is_err());
+        assert!(builder.clone().in_set([]).build().is_err());
+        assert!(builder.lookup_table_entry(&[], 1).build().is_err());
+    }
+
+    #[test]
+    fn value_set_builder_accumulates_values() {
+        let set = PossibleValueSetBuilder::new()
+            .in_set([1, 2])
+            .in_set([3])
+            .build();
+        assert_eq!(
+            set,
+            Ok(PossibleValueSet::InSetOfValues {
+                values: HashSet::from([1, 2, 3])
+            })
+        );
+    }
+
+    #[test]
+    fn value_set_builder_offsets_only_ranges() {
+        let set = PossibleValueSetBuilder::new()
+            .unsigned_range(0, 4, 1)
+            .offset(8)
+            .build();
+        assert!(matches!(
+            set,
+            Ok(PossibleValueSet::UnsignedRangeValue { offset: 8, .. })
+        ));
+        let set = PossibleValueSetBuilder::new().constant(1).offset(8).build();
+        assert!(set.is_err());
+    }
+
+    #[test]
+    fn variables_order_by_source_then_storage_then_index() {
+        use BNVariableSourceType::*;
+        let mut vars = vec![
+            Variable::new(RegisterVariableSourceType, 0, 1),
+            Variable::new(StackVariableSourceType, 1, -8),
+            Variable::new(StackVariableSourceType, 0, -8),
+            Variable::new(StackVariableSourceType, 0, -16),
+        ];
+        vars.sort();
+        assert_eq!(
+            vars,
+            [
+                Variable::new(StackVariableSourceType, 0, -16),
+                Variable::new(StackVariableSourceType, 0, -8),
+                Variable::new(StackVariableSourceType, 1, -8),
+                Variable::new(RegisterVariableSourceType, 0, 1),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn value_sets_round_trip_through_serde() {
+        let sets = [
+            PossibleValueSet::UndeterminedValue,
+            PossibleValueSet::ExternalPointerValue {
+                value: 0x1000,
+                offset: -4,
+            },
+            PossibleValueSet::SignedRangeValue {
+                offset: 0,
+                ranges: vec![ValueRange::from_raw(BNValueRange {
+                    start: -8i64 as u64,
+                    end: 8,
+                    step: 4,
+                })],
+            },
+            PossibleValueSet::LookupTableValue {
+                tables: vec![LookupTableEntry {
+                    from_values: vec![1, 2],
+                    to_value: 10,
+                }],
+            },
+            PossibleValueSet::InSetOfValues {
+                values: HashSet::from([1, 3, 5]),
+            },
+            PossibleValueSet::ConstantDataValue {
+                value_type: ConstantDataType::SignExtend,
+                value: -1,
+            },
+        ];
+        for set in sets {
+            let json = serde_json::to_string(&set).unwrap();
+            let back: PossibleValueSet = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, set);
+        }
+    }
+}